@@ -0,0 +1,179 @@
+//! Size categories, such as "(small)" and "(pretty big)", and the
+//! thresholds at which they apply.
+
+use colored::{Color, ColoredString, Colorize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A description that applies to numbers whose magnitude is at least
+/// `min`, until the next category's `min`.
+#[derive(Clone, Debug)]
+pub struct Category {
+    pub min: f64,
+    pub label: String,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub dimmed: bool,
+}
+
+impl Style {
+    pub fn apply(&self, text: &str) -> ColoredString {
+        let mut styled = text.normal();
+        if let Some(color) = self.color {
+            styled = styled.color(color);
+        }
+        if self.bold {
+            styled = styled.bold();
+        }
+        if self.dimmed {
+            styled = styled.dimmed();
+        }
+        styled
+    }
+}
+
+impl Category {
+    fn new(min: f64, label: &str, color: Color, bold: bool) -> Self {
+        Category {
+            min,
+            label: label.to_string(),
+            style: Style {
+                color: Some(color),
+                bold,
+                dimmed: false,
+            },
+        }
+    }
+}
+
+/// The categories used when the user hasn't configured their own.
+pub fn default_categories() -> Vec<Category> {
+    vec![
+        Category::new(0.0, "microscopic", Color::BrightBlue, false),
+        Category::new(0.001, "tiny", Color::BrightBlue, false),
+        Category::new(1.0, "small", Color::BrightCyan, false),
+        Category::new(1_000.0, "medium", Color::BrightGreen, false),
+        Category::new(1_000_000.0, "pretty big", Color::BrightYellow, false),
+        Category::new(1e9, "extremely big", Color::BrightRed, true),
+        Category::new(1e12, "gigantic", Color::BrightMagenta, true),
+        Category::new(1e15, "astronomical", Color::BrightMagenta, true),
+        Category::new(1e18, "cosmic", Color::BrightMagenta, true),
+    ]
+}
+
+/// Find the category for `number`. `categories` must be sorted by
+/// `min`. Returns `None` if the number is smaller than every threshold.
+pub fn find_category(categories: &[Category], number: f64) -> Option<&Category> {
+    let abs_value = number.abs();
+    categories.iter().rev().find(|c| abs_value >= c.min)
+}
+
+/// A stable machine-readable name for `number`'s size category: its
+/// label in lowercase with spaces and punctuation replaced by
+/// underscores, e.g. `pretty_big`. Zero is always `zero`.
+pub fn category_name(number: f64, categories: &[Category]) -> Option<String> {
+    if number == 0.0 {
+        return Some("zero".to_string());
+    }
+
+    let category = find_category(categories, number)?;
+    let name = category
+        .label
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    Some(name)
+}
+
+/// Parse a category of the form `MIN:LABEL[:STYLE]`, e.g.
+/// `1e6:huge:bright_red+bold`.
+///
+/// MIN may use `_` or `,` as digit separators. STYLE is a list of
+/// attributes separated by `+`, each being a color name or `bold` or
+/// `dimmed`.
+pub fn parse_category(src: &str) -> Result<Category, String> {
+    let mut parts = src.splitn(3, ':');
+    let min_src = parts.next().unwrap_or_default().trim();
+    let label = parts.next().map(str::trim).unwrap_or_default();
+    let style_src = parts.next().unwrap_or_default();
+
+    let min = min_src
+        .replace(['_', ','], "")
+        .parse::<f64>()
+        .ok()
+        .filter(|min| *min >= 0.0)
+        .ok_or_else(|| format!("invalid threshold '{}'", min_src))?;
+
+    if label.is_empty() {
+        return Err(format!(
+            "missing label in '{}' (expected MIN:LABEL[:STYLE])",
+            src
+        ));
+    }
+
+    Ok(Category {
+        min,
+        label: label.to_string(),
+        style: parse_style(style_src)?,
+    })
+}
+
+fn parse_style(src: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+
+    for attribute in src.split('+').map(str::trim).filter(|a| !a.is_empty()) {
+        match attribute.to_lowercase().as_str() {
+            "bold" => style.bold = true,
+            "dimmed" => style.dimmed = true,
+            name => {
+                let color = name
+                    .replace(['_', '-'], " ")
+                    .parse::<Color>()
+                    .map_err(|_| format!("unknown color '{}'", attribute))?;
+                style.color = Some(color);
+            }
+        }
+    }
+
+    Ok(style)
+}
+
+/// Read categories from a file containing one category per line, in the
+/// same format as --category. Blank lines and lines starting with `#`
+/// are ignored.
+pub fn load_category_file(path: &Path) -> io::Result<Vec<Category>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+    let mut categories = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let category = parse_category(line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: {}", path.display(), i + 1, e),
+            )
+        })?;
+        categories.push(category);
+    }
+
+    Ok(categories)
+}
+
+/// Sort categories by threshold, so they can be passed to
+/// `find_category`.
+pub fn sort_categories(categories: &mut [Category]) {
+    categories.sort_by(|a, b| a.min.total_cmp(&b.min));
+}
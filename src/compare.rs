@@ -0,0 +1,200 @@
+//! Comparisons with familiar quantities, such as "≈ 3 × a DVD".
+
+use crate::i18n::{self, Lang};
+use crate::kinds::Kind;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A familiar quantity that numbers can be compared against.
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub value: f64,
+    pub name: String,
+}
+
+impl Reference {
+    fn new(value: f64, name: &str) -> Self {
+        Reference {
+            value,
+            name: name.to_string(),
+        }
+    }
+}
+
+/// The built-in reference quantities for `kind`.
+pub fn default_references(kind: Kind) -> Vec<Reference> {
+    match kind {
+        Kind::Number => vec![
+            Reference::new(12.0, "a dozen"),
+            Reference::new(365.0, "the days in a year"),
+            Reference::new(60_000.0, "a football stadium crowd"),
+            Reference::new(8_900_000.0, "the population of London"),
+            Reference::new(83_000_000.0, "the population of Germany"),
+            Reference::new(330_000_000.0, "the population of the USA"),
+            Reference::new(8e9, "the population of the world"),
+            Reference::new(86e9, "the neurons in a human brain"),
+            Reference::new(37e12, "the cells in a human body"),
+        ],
+        Kind::Bytes => vec![
+            Reference::new(280.0, "a tweet"),
+            Reference::new(1_474_560.0, "a floppy disk"),
+            Reference::new(5e6, "an MP3 song"),
+            Reference::new(734_003_200.0, "a CD"),
+            Reference::new(4.7e9, "a DVD"),
+            Reference::new(25e9, "a Blu-ray disc"),
+            Reference::new(1e12, "a 1 TB hard drive"),
+        ],
+        Kind::Duration => vec![
+            Reference::new(1.0, "a heartbeat"),
+            Reference::new(210.0, "a pop song"),
+            Reference::new(5_400.0, "a feature film"),
+            Reference::new(86_400.0, "a day"),
+            Reference::new(31_557_600.0, "a year"),
+            Reference::new(2.5e9, "a human lifetime"),
+        ],
+        Kind::Money => vec![
+            Reference::new(5.0, "a cup of coffee"),
+            Reference::new(1_000.0, "a smartphone"),
+            Reference::new(30_000.0, "a new car"),
+            Reference::new(400_000.0, "a house"),
+            Reference::new(300e6, "a blockbuster film budget"),
+        ],
+        Kind::Ratio => vec![
+            Reference::new(1.0 / 649_740.0, "drawing a royal flush"),
+            Reference::new(1.0 / 365.0, "a random day being your birthday"),
+            Reference::new(1.0 / 52.0, "drawing the ace of spades"),
+            Reference::new(1.0 / 6.0, "rolling a six"),
+            Reference::new(0.5, "a coin landing heads"),
+        ],
+    }
+}
+
+/// Parse a reference of the form `VALUE:NAME`, e.g. `4.7e9:a DVD`.
+pub fn parse_reference(src: &str) -> Result<Reference, String> {
+    let (value_src, name) = src
+        .split_once(':')
+        .ok_or_else(|| format!("expected VALUE:NAME, got '{}'", src))?;
+
+    let value = value_src
+        .trim()
+        .replace(['_', ','], "")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| *value > 0.0 && value.is_finite())
+        .ok_or_else(|| format!("invalid reference value '{}'", value_src.trim()))?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing name in '{}'", src));
+    }
+
+    Ok(Reference::new(value, name))
+}
+
+/// Read references for `kind` from a file containing lines of the form
+/// `KIND:VALUE:NAME`. Blank lines and lines starting with `#` are
+/// ignored.
+pub fn load_reference_file(path: &Path, kind: Kind) -> io::Result<Vec<Reference>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+    let mut references = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |e: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: {}", path.display(), i + 1, e),
+            )
+        };
+
+        let (kind_src, reference_src) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(format!("expected KIND:VALUE:NAME, got '{}'", line)))?;
+        let line_kind = <Kind as clap::ValueEnum>::from_str(kind_src.trim(), true)
+            .map_err(|_| invalid(format!("unknown kind '{}'", kind_src.trim())))?;
+        let reference = parse_reference(reference_src).map_err(invalid)?;
+
+        if line_kind == kind {
+            references.push(reference);
+        }
+    }
+
+    Ok(references)
+}
+
+/// Compare `number` with the closest reference quantity, e.g.
+/// "≈ 3 × a DVD", in `lang`. Names of references that aren't built in
+/// are left as they are.
+///
+/// Whole multiples are easier to picture than fractions, so unless a
+/// reference is within 10% of the number, we use the largest reference
+/// that's no bigger than it. Numbers smaller than every reference are
+/// compared with the smallest, unless they're less than a thousandth of
+/// it, which isn't a useful comparison.
+pub fn compare(number: f64, references: &[Reference], lang: Lang) -> Option<String> {
+    let abs_value = number.abs();
+    if abs_value == 0.0 || !abs_value.is_finite() {
+        return None;
+    }
+
+    let by_value = |a: &&Reference, b: &&Reference| a.value.total_cmp(&b.value);
+    let close_enough = |reference: &&Reference| (abs_value / reference.value - 1.0).abs() <= 0.1;
+
+    if let Some(reference) = references.iter().find(close_enough) {
+        let name = i18n::translate(lang, &reference.name);
+        return Some(i18n::translate_with(lang, "≈ {}", name));
+    }
+
+    let closest = references
+        .iter()
+        .filter(|reference| reference.value <= abs_value)
+        .max_by(by_value)
+        .or_else(|| references.iter().min_by(by_value))?;
+
+    let ratio = abs_value / closest.value;
+    if ratio < 0.001 {
+        return None;
+    }
+
+    Some(
+        i18n::translate(lang, "≈ {ratio} × {reference}")
+            .replace("{ratio}", &format_ratio(ratio))
+            .replace("{reference}", i18n::translate(lang, &closest.name)),
+    )
+}
+
+/// Format a ratio to roughly two significant figures: 0.25, 3.5, 42,
+/// 1,300, or 2.7×10^19 for ratios too long to write out.
+fn format_ratio(ratio: f64) -> String {
+    use num_format::{Locale, ToFormattedString};
+
+    if ratio >= 1e15 {
+        let mut exponent = ratio.log10().floor() as i32;
+        let mut mantissa = (ratio / 10f64.powi(exponent) * 10.0).round() / 10.0;
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        format!("{}×10^{}", mantissa, exponent)
+    } else if ratio >= 10.0 {
+        let digits = ratio.log10().floor() as i32;
+        let scale = 10f64.powi(digits - 1);
+        let rounded = ((ratio / scale).round() * scale) as u64;
+        rounded.to_formatted_string(&Locale::en)
+    } else if ratio >= 1.0 {
+        let rounded = (ratio * 10.0).round() / 10.0;
+        format!("{}", rounded)
+    } else {
+        let decimals = (1 - ratio.log10().floor() as i32) as usize;
+        format!("{:.*}", decimals, ratio)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
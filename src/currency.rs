@@ -0,0 +1,178 @@
+//! Amounts of money in a particular currency, such as $1,234.50 or
+//! ¥1,235, for --currency.
+
+use crate::digits;
+use crate::i18n::Lang;
+
+/// A currency, with the number of decimal places ISO 4217 gives it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Currency {
+    pub code: &'static str,
+    symbol: &'static str,
+    /// The symbol used for this currency in running text, if any. Several
+    /// currencies share "$", so it's only read as e.g. Canadian dollars
+    /// with --currency CAD.
+    text_symbol: Option<&'static str>,
+    minor_units: u32,
+}
+
+const fn currency(
+    code: &'static str,
+    symbol: &'static str,
+    text_symbol: Option<&'static str>,
+    minor_units: u32,
+) -> Currency {
+    Currency {
+        code,
+        symbol,
+        text_symbol,
+        minor_units,
+    }
+}
+
+/// The currencies we know about. The first currency with a given text
+/// symbol is the one it means by default.
+const CURRENCIES: [Currency; 27] = [
+    currency("USD", "$", Some("$"), 2),
+    currency("EUR", "€", Some("€"), 2),
+    currency("GBP", "£", Some("£"), 2),
+    currency("JPY", "¥", Some("¥"), 0),
+    currency("INR", "₹", Some("₹"), 2),
+    currency("KRW", "₩", Some("₩"), 0),
+    currency("CNY", "CN¥", Some("¥"), 2),
+    currency("CAD", "CA$", Some("$"), 2),
+    currency("AUD", "A$", Some("$"), 2),
+    currency("NZD", "NZ$", Some("$"), 2),
+    currency("HKD", "HK$", Some("$"), 2),
+    currency("MXN", "MX$", Some("$"), 2),
+    currency("BRL", "R$", None, 2),
+    currency("CHF", "CHF", None, 2),
+    currency("SEK", "SEK", None, 2),
+    currency("NOK", "NOK", None, 2),
+    currency("DKK", "DKK", None, 2),
+    currency("PLN", "zł", None, 2),
+    currency("ZAR", "R", None, 2),
+    currency("ISK", "ISK", None, 0),
+    currency("VND", "₫", None, 0),
+    currency("CLP", "CLP", None, 0),
+    currency("BHD", "BHD", None, 3),
+    currency("KWD", "KWD", None, 3),
+    currency("OMR", "OMR", None, 3),
+    currency("JOD", "JOD", None, 3),
+    currency("TND", "TND", None, 3),
+];
+
+/// Matches the currency symbols recognised in text, e.g. "$" in
+/// "$1234.5".
+pub const SYMBOL_PATTERN: &str = "[$€£¥₹₩]";
+
+/// Parse a currency code given with --currency, e.g. "usd".
+pub fn parse_currency(src: &str) -> Result<&'static Currency, String> {
+    CURRENCIES
+        .iter()
+        .find(|currency| currency.code.eq_ignore_ascii_case(src.trim()))
+        .ok_or_else(|| {
+            let codes: Vec<&str> = CURRENCIES.iter().map(|currency| currency.code).collect();
+            format!(
+                "unknown currency '{}', expected one of {}",
+                src,
+                codes.join(", ")
+            )
+        })
+}
+
+/// Settings for --currency.
+#[derive(Clone, Copy, Debug)]
+pub struct CurrencyFormat {
+    pub currency: &'static Currency,
+    /// Show negative amounts in parentheses, e.g. ($5.00).
+    pub accounting: bool,
+}
+
+impl CurrencyFormat {
+    /// The currency that `symbol` means in text: ours if it uses that
+    /// symbol, otherwise the most common currency that does.
+    pub fn currency_for_symbol(&self, symbol: &str) -> Option<&'static Currency> {
+        if self.currency.text_symbol == Some(symbol) {
+            return Some(self.currency);
+        }
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.text_symbol == Some(symbol))
+    }
+
+    /// Parse an amount written with a currency symbol, e.g. "$1234.5",
+    /// "$1,234.50" or "-€5", returning the amount and its currency.
+    pub fn parse_amount(&self, text: &str) -> Option<(f64, &'static Currency)> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        let symbol = text.chars().next()?;
+        let currency = self.currency_for_symbol(&symbol.to_string())?;
+        let number: f64 = digits::to_ascii(text[symbol.len_utf8()..].trim_start())
+            .replace(',', "")
+            .parse()
+            .ok()?;
+        Some((if negative { -number } else { number }, currency))
+    }
+
+    /// Would `amount` be rounded to fit the currency's minor units?
+    pub fn is_rounded(&self, amount: f64) -> bool {
+        let minor = amount * 10f64.powi(self.currency.minor_units as i32);
+        (minor - minor.round()).abs() > 1e-6
+    }
+
+    /// Format `amount` rounded to the currency's minor units, with its
+    /// symbol placed as usual for `lang`: $1,234.50 in English, 1,234.50
+    /// € in German. `group` formats the whole part of the amount with
+    /// separators.
+    pub fn format(&self, amount: f64, lang: Lang, group: impl Fn(f64) -> String) -> String {
+        if !amount.is_finite() {
+            return format!("{} {}", amount, self.currency.code);
+        }
+
+        // Amounts from 2^53 up have no fractional part, and converting
+        // them to minor units would only add float error.
+        let minor_per_unit = 10u128.pow(self.currency.minor_units);
+        let (whole, minor) = if amount.abs() >= 2f64.powi(53) {
+            (amount.abs(), 0)
+        } else {
+            let minor = (amount.abs() * minor_per_unit as f64).round() as u128;
+            ((minor / minor_per_unit) as f64, minor % minor_per_unit)
+        };
+        let mut digits = group(whole);
+        if self.currency.minor_units > 0 {
+            digits.push_str(&format!(
+                ".{:0width$}",
+                minor,
+                width = self.currency.minor_units as usize
+            ));
+        }
+
+        let symbol = self.currency.symbol;
+        let with_symbol = match lang {
+            Lang::En | Lang::Ja => {
+                // Letters need a space before the digits: CHF 5.00.
+                let space = if symbol.ends_with(|c: char| c.is_alphabetic()) {
+                    " "
+                } else {
+                    ""
+                };
+                format!("{}{}{}", symbol, space, digits)
+            }
+            Lang::De | Lang::Fr | Lang::Es => format!("{} {}", digits, symbol),
+        };
+
+        // Don't show -$0.00 for tiny negative amounts.
+        if amount >= 0.0 || (whole == 0.0 && minor == 0) {
+            with_symbol
+        } else if self.accounting {
+            format!("({})", with_symbol)
+        } else {
+            format!("-{}", with_symbol)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Digits from scripts other than Latin, such as Arabic-Indic ٠١٢ or
+//! Devanagari ०१२.
+
+use crate::i18n;
+use clap::ValueEnum;
+use std::borrow::Cow;
+
+/// A regex character class matching a decimal digit in any supported
+/// script.
+pub const DIGIT_CLASS: &str = "[0-9٠-٩۰-۹०-९০-৯๐-๙０-９]";
+
+/// The scripts whose digits we recognise, and can use in output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DigitScript {
+    /// 0123456789
+    Latin,
+    /// ٠١٢٣٤٥٦٧٨٩, as used in Arabic
+    ArabicIndic,
+    /// ۰۱۲۳۴۵۶۷۸۹, as used in Persian and Urdu
+    EasternArabic,
+    /// ०१२३४५६७८९, as used in Hindi and Marathi
+    Devanagari,
+    /// ০১২৩৪৫৬৭৮৯
+    Bengali,
+    /// ๐๑๒๓๔๕๖๗๘๙
+    Thai,
+    /// ０１２３４５６７８９
+    Fullwidth,
+    /// The native digits of the locale in LC_ALL, LC_MESSAGES or LANG
+    Locale,
+}
+
+impl DigitScript {
+    /// The character for zero, with the other digits following it.
+    fn zero(self) -> char {
+        match self {
+            DigitScript::Latin | DigitScript::Locale => '0',
+            DigitScript::ArabicIndic => '٠',
+            DigitScript::EasternArabic => '۰',
+            DigitScript::Devanagari => '०',
+            DigitScript::Bengali => '০',
+            DigitScript::Thai => '๐',
+            DigitScript::Fullwidth => '０',
+        }
+    }
+
+    /// Resolve `Locale` to the digits used by the user's locale.
+    pub fn resolve(self) -> DigitScript {
+        if self != DigitScript::Locale {
+            return self;
+        }
+
+        let locale = i18n::env_locale().unwrap_or_default();
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default();
+        match language {
+            "ar" => DigitScript::ArabicIndic,
+            "fa" | "ur" => DigitScript::EasternArabic,
+            "hi" | "mr" | "ne" => DigitScript::Devanagari,
+            "bn" => DigitScript::Bengali,
+            "th" => DigitScript::Thai,
+            _ => DigitScript::Latin,
+        }
+    }
+
+    /// Write the ASCII digits in `text` in this script.
+    pub fn render(self, text: &str) -> String {
+        let zero = self.zero();
+        if zero == '0' {
+            return text.to_string();
+        }
+
+        text.chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) if c.is_ascii_digit() => {
+                    char::from_u32(zero as u32 + digit).unwrap_or(c)
+                }
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+/// The value of `c` if it's a digit in any supported script.
+fn digit_value(c: char) -> Option<u32> {
+    ['0', '٠', '۰', '०', '০', '๐', '０']
+        .iter()
+        .find_map(|zero| {
+            let value = (c as u32).checked_sub(*zero as u32)?;
+            (value < 10).then_some(value)
+        })
+}
+
+/// Replace digits from other scripts in `text` with ASCII digits, so it
+/// can be parsed. The Arabic decimal separator ٫ becomes `.`.
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    text.chars()
+        .map(|c| match digit_value(c) {
+            Some(digit) => char::from_digit(digit, 10).unwrap_or(c),
+            None if c == '٫' => '.',
+            None => c,
+        })
+        .collect()
+}
@@ -0,0 +1,237 @@
+//! Durations such as "1d 2h 3m 4s" or "1.5 ms", for --duration.
+
+use clap::ValueEnum;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A unit of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum TimeUnit {
+    /// Nanoseconds
+    Ns,
+    /// Microseconds
+    Us,
+    /// Milliseconds
+    Ms,
+    /// Seconds
+    S,
+    /// Minutes
+    M,
+    /// Hours
+    H,
+    /// Days
+    D,
+    /// Weeks
+    W,
+}
+
+/// All units, largest first.
+const UNITS: [TimeUnit; 8] = [
+    TimeUnit::W,
+    TimeUnit::D,
+    TimeUnit::H,
+    TimeUnit::M,
+    TimeUnit::S,
+    TimeUnit::Ms,
+    TimeUnit::Us,
+    TimeUnit::Ns,
+];
+
+impl TimeUnit {
+    fn nanoseconds(self) -> u128 {
+        match self {
+            TimeUnit::Ns => 1,
+            TimeUnit::Us => 1_000,
+            TimeUnit::Ms => 1_000_000,
+            TimeUnit::S => 1_000_000_000,
+            TimeUnit::M => 60 * 1_000_000_000,
+            TimeUnit::H => 60 * 60 * 1_000_000_000,
+            TimeUnit::D => 24 * 60 * 60 * 1_000_000_000,
+            TimeUnit::W => 7 * 24 * 60 * 60 * 1_000_000_000,
+        }
+    }
+
+    /// The length of this unit in seconds.
+    pub fn seconds(self) -> f64 {
+        self.nanoseconds() as f64 / 1e9
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            TimeUnit::Ns => "ns",
+            TimeUnit::Us => "µs",
+            TimeUnit::Ms => "ms",
+            TimeUnit::S => "s",
+            TimeUnit::M => "m",
+            TimeUnit::H => "h",
+            TimeUnit::D => "d",
+            TimeUnit::W => "w",
+        }
+    }
+}
+
+/// Settings for --duration.
+#[derive(Clone, Copy, Debug)]
+pub struct DurationFormat {
+    /// The unit of the input numbers.
+    pub unit: TimeUnit,
+    /// The largest unit to show, e.g. 26h rather than 1d 2h.
+    pub largest: TimeUnit,
+    /// The smallest unit to show. If `None`, durations of a second or
+    /// more stop at seconds, and shorter ones use a single unit.
+    pub smallest: Option<TimeUnit>,
+    /// The number of decimal places for the smallest unit.
+    pub precision: usize,
+}
+
+impl DurationFormat {
+    /// Format `number` (in `self.unit`) as e.g. "1d 2h 3m 4s", or "1.5 ms"
+    /// for durations under a second.
+    pub fn format(&self, number: f64) -> String {
+        let nanoseconds = number.abs() * self.unit.nanoseconds() as f64;
+        // Durations too long to count in nanoseconds aren't useful to
+        // break down.
+        if !nanoseconds.is_finite() || nanoseconds >= 1e30 {
+            return number.to_string();
+        }
+        let sign = if number < 0.0 { "-" } else { "" };
+
+        let smallest = self.smallest.unwrap_or_else(|| {
+            // The largest unit that fits, but no larger than seconds.
+            let seconds = TimeUnit::S.min(self.largest);
+            if nanoseconds == 0.0 {
+                return seconds;
+            }
+            UNITS
+                .iter()
+                .copied()
+                .filter(|unit| *unit <= seconds)
+                .find(|unit| unit.nanoseconds() as f64 <= nanoseconds)
+                .unwrap_or(TimeUnit::Ns)
+        });
+        let largest = self.largest.max(smallest);
+
+        // Count in steps of the smallest unit's last decimal place, so
+        // rounding carries into larger units: 59.999s is 1m.
+        let scale = 10u128.pow(self.precision as u32);
+        let step = smallest.nanoseconds() * scale;
+        let steps = (nanoseconds / smallest.nanoseconds() as f64 * scale as f64).round() as u128;
+        // In units of 1/scale nanoseconds.
+        let mut remaining = steps * smallest.nanoseconds();
+
+        let mut parts = vec![];
+        for unit in UNITS
+            .iter()
+            .filter(|unit| (smallest..=largest).contains(*unit))
+        {
+            let size = unit.nanoseconds() * scale;
+            if *unit == smallest {
+                let count = format_decimal(remaining, step, self.precision);
+                if count != "0" || parts.is_empty() {
+                    parts.push((count, *unit));
+                }
+            } else {
+                let count = remaining / size;
+                remaining %= size;
+                if count > 0 {
+                    parts.push((count.to_string(), *unit));
+                }
+            }
+        }
+
+        // A sub-second duration shown as a single unit reads better
+        // with a space, e.g. "1.5 ms".
+        if let [(count, unit)] = parts.as_slice()
+            && *unit < TimeUnit::S
+        {
+            return format!("{}{} {}", sign, count, unit.symbol());
+        }
+
+        let formatted: Vec<String> = parts
+            .iter()
+            .map(|(count, unit)| format!("{}{}", count, unit.symbol()))
+            .collect();
+        format!("{}{}", sign, formatted.join(" "))
+    }
+}
+
+/// Format `value / step` with up to `precision` decimal places, without
+/// trailing zeros.
+fn format_decimal(value: u128, step: u128, precision: usize) -> String {
+    let scale = 10u128.pow(precision as u32);
+    let whole = value / step;
+    let fraction = value % step * scale / step;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let decimals = format!("{:0width$}", fraction, width = precision);
+    format!("{}.{}", whole, decimals.trim_end_matches('0'))
+}
+
+/// Matches a unit of time after a number in text, e.g. "ms" or
+/// "seconds". Single letters like "m" and "d" are left out, as "5 m" is
+/// more likely to be metres than minutes.
+pub const TEXT_UNIT_PATTERN: &str = concat!(
+    "nanoseconds?|ns|microseconds?|us|µs|μs|milliseconds?|msecs?|ms",
+    "|seconds?|secs?|s|minutes?|mins?|hours?|hrs?|h|days?|weeks?",
+);
+
+/// The unit of time named by `unit`, which was matched by
+/// `TEXT_UNIT_PATTERN`.
+pub fn text_unit(unit: &str) -> Option<TimeUnit> {
+    let unit = match unit {
+        "ns" | "nanosecond" | "nanoseconds" => TimeUnit::Ns,
+        "us" | "µs" | "μs" | "microsecond" | "microseconds" => TimeUnit::Us,
+        "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => TimeUnit::Ms,
+        "s" | "sec" | "secs" | "second" | "seconds" => TimeUnit::S,
+        "min" | "mins" | "minute" | "minutes" => TimeUnit::M,
+        "h" | "hr" | "hrs" | "hour" | "hours" => TimeUnit::H,
+        "day" | "days" => TimeUnit::D,
+        "week" | "weeks" => TimeUnit::W,
+        _ => return None,
+    };
+    Some(unit)
+}
+
+static DURATION_PART_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([0-9]+(?:\.[0-9]+)?)\s*(ns|us|µs|ms|s|m|h|d|w)").unwrap());
+
+/// Parse a duration such as "1h30m" or "1d 2h 3m 4s" as a number of
+/// seconds.
+pub fn parse_duration(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+
+    let mut seconds = 0.0;
+    let mut end = 0;
+    for found in DURATION_PART_RE.captures_iter(text) {
+        let whole = found.get(0)?;
+        // Only whitespace is allowed between parts.
+        if !text[end..whole.start()].trim().is_empty() {
+            return None;
+        }
+        end = whole.end();
+
+        let count: f64 = found[1].parse().ok()?;
+        let unit = match &found[2] {
+            "ns" => TimeUnit::Ns,
+            "us" | "µs" => TimeUnit::Us,
+            "ms" => TimeUnit::Ms,
+            "s" => TimeUnit::S,
+            "m" => TimeUnit::M,
+            "h" => TimeUnit::H,
+            "d" => TimeUnit::D,
+            _ => TimeUnit::W,
+        };
+        seconds += count * unit.seconds();
+    }
+
+    if end == 0 || !text[end..].trim().is_empty() {
+        return None;
+    }
+    Some(if negative { -seconds } else { seconds })
+}
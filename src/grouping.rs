@@ -0,0 +1,121 @@
+//! Digit grouping styles, such as 1,234,567, the Indian 12,34,567 or the
+//! East Asian 123万4567.
+
+use crate::i18n;
+use clap::ValueEnum;
+
+/// How digits are grouped, which also decides the names used for large
+/// numbers in compact output and words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Grouping {
+    /// Groups of three: 12,345,678 or 12.3M
+    Western,
+    /// Three digits, then groups of two: 1,23,45,678 or 1.2 crore
+    Indian,
+    /// Groups of four with Chinese and Japanese units: 1億2345万6789 or
+    /// 1.2億
+    Myriad,
+    /// Groups of four with Korean units: 1억 2345만 6789 or 1.2억
+    MyriadHangul,
+}
+
+impl Grouping {
+    /// The grouping used in the user's locale: Indian for `en_IN` and
+    /// `hi_IN`, otherwise Western.
+    pub fn from_env() -> Grouping {
+        match i18n::env_locale() {
+            Some(locale) if is_indian_locale(&locale) => Grouping::Indian,
+            _ => Grouping::Western,
+        }
+    }
+}
+
+/// Is `locale` (e.g. `en_IN.UTF-8` or `hi-IN`) one that uses Indian
+/// grouping?
+fn is_indian_locale(locale: &str) -> bool {
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    matches!(
+        name.replace('-', "_").to_lowercase().as_str(),
+        "en_in" | "hi_in"
+    )
+}
+
+/// The names of 10^4, 10^8, 10^12 etc in Chinese and Japanese.
+const CJK_MYRIADS: [&str; 5] = ["万", "億", "兆", "京", "垓"];
+
+/// The names of 10^4, 10^8, 10^12 etc in Korean.
+const HANGUL_MYRIADS: [&str; 5] = ["만", "억", "조", "경", "해"];
+
+/// The names of 10^4, 10^8, 10^12 etc for a myriad grouping style.
+pub fn myriad_units(grouping: Grouping) -> &'static [&'static str] {
+    match grouping {
+        Grouping::MyriadHangul => &HANGUL_MYRIADS,
+        _ => &CJK_MYRIADS,
+    }
+}
+
+/// Group the digits of a whole number, e.g. "1234567" as 1,234,567,
+/// 12,34,567 or 123万4567. `digits` has no sign.
+pub fn group_digits(digits: &str, grouping: Grouping) -> String {
+    match grouping {
+        Grouping::Western => separate(digits, 3, 3),
+        Grouping::Indian => separate(digits, 3, 2),
+        Grouping::Myriad | Grouping::MyriadHangul => format_myriad(digits, grouping),
+    }
+}
+
+/// Separate `digits` with commas, after the last `first` digits and then
+/// every `rest` digits.
+fn separate(digits: &str, first: usize, rest: usize) -> String {
+    let mut groups: Vec<&str> = vec![];
+    let mut remaining = digits;
+    let mut size = first;
+    while remaining.len() > size {
+        let (high, low) = remaining.split_at(remaining.len() - size);
+        groups.push(low);
+        remaining = high;
+        size = rest;
+    }
+    groups.push(remaining);
+    groups.reverse();
+    groups.join(",")
+}
+
+/// Format a whole number in groups of four digits, each followed by its
+/// unit, e.g. 1億2345万6789 or 1억 2345만 6789. Groups of zeros are
+/// left out, so 10^8 is just 1億.
+fn format_myriad(digits: &str, grouping: Grouping) -> String {
+    let units = myriad_units(grouping);
+
+    // Least significant first. Anything beyond the largest unit stays
+    // in the final group.
+    let mut groups: Vec<&str> = vec![];
+    let mut rest = digits;
+    while rest.len() > 4 && groups.len() < units.len() {
+        let (high, low) = rest.split_at(rest.len() - 4);
+        groups.push(low);
+        rest = high;
+    }
+    groups.push(rest);
+
+    let mut parts = vec![];
+    for (power, group) in groups.iter().enumerate().rev() {
+        let group = group.trim_start_matches('0');
+        if group.is_empty() {
+            continue;
+        }
+        let unit = if power == 0 { "" } else { units[power - 1] };
+        parts.push(format!("{}{}", group, unit));
+    }
+
+    // Korean puts spaces between groups.
+    let separator = match grouping {
+        Grouping::MyriadHangul => " ",
+        _ => "",
+    };
+    if parts.is_empty() {
+        "0".to_string()
+    } else {
+        parts.join(separator)
+    }
+}
@@ -0,0 +1,651 @@
+//! Translations of nn's descriptions into other languages.
+//!
+//! Messages are looked up by their English text, so anything without a
+//! translation (including user-defined category labels) is shown as
+//! given.
+
+use clap::ValueEnum;
+use std::env;
+
+/// The language used for descriptions and number words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// German (Deutsch)
+    De,
+    /// French (français)
+    Fr,
+    /// Spanish (español)
+    Es,
+    /// Japanese (日本語)
+    Ja,
+}
+
+impl Lang {
+    /// The language of the user's locale, from LC_ALL, LC_MESSAGES or
+    /// LANG, in that order. Unsupported languages fall back to English.
+    pub fn from_env() -> Lang {
+        env_locale()
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or(Lang::En)
+    }
+
+    /// The language of a locale name such as `de_DE.UTF-8` or `fr-CA`.
+    fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        match language.to_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "de" => Some(Lang::De),
+            "fr" => Some(Lang::Fr),
+            "es" => Some(Lang::Es),
+            "ja" => Some(Lang::Ja),
+            _ => None,
+        }
+    }
+}
+
+/// The user's locale name, e.g. `de_DE.UTF-8`, from the first of
+/// LC_ALL, LC_MESSAGES and LANG that's set.
+pub fn env_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+}
+
+/// Translate `message` into `lang`, or return it unchanged if there's
+/// no translation.
+pub fn translate(lang: Lang, message: &str) -> &str {
+    let catalog = match lang {
+        Lang::En => return message,
+        Lang::De => GERMAN,
+        Lang::Fr => FRENCH,
+        Lang::Es => SPANISH,
+        Lang::Ja => JAPANESE,
+    };
+
+    catalog
+        .iter()
+        .find(|(english, _)| *english == message)
+        .map_or(message, |(_, translated)| translated)
+}
+
+/// Translate a message containing a `{}` placeholder, then fill it in.
+pub fn translate_with(lang: Lang, message: &str, value: &str) -> String {
+    translate(lang, message).replace("{}", value)
+}
+
+type Catalog = &'static [(&'static str, &'static str)];
+
+const GERMAN: Catalog = &[
+    // Size categories
+    ("zero", "null"),
+    ("microscopic", "mikroskopisch"),
+    ("tiny", "winzig"),
+    ("small", "klein"),
+    ("medium", "mittel"),
+    ("pretty big", "ziemlich groß"),
+    ("extremely big", "sehr groß"),
+    ("gigantic", "gigantisch"),
+    ("astronomical", "astronomisch"),
+    ("cosmic", "kosmisch"),
+    ("rounded", "gerundet"),
+    // Bytes
+    ("smaller than a disk block", "kleiner als ein Plattenblock"),
+    ("fits in CPU cache", "passt in den CPU-Cache"),
+    ("fits in RAM", "passt in den Arbeitsspeicher"),
+    ("fits on a disk", "passt auf eine Festplatte"),
+    ("data centre scale", "Rechenzentrumsmaßstab"),
+    // Durations
+    ("less than a second", "weniger als eine Sekunde"),
+    ("about {} year", "etwa {} Jahr"),
+    ("about {} years", "etwa {} Jahre"),
+    ("about {} month", "etwa {} Monat"),
+    ("about {} months", "etwa {} Monate"),
+    ("about {} week", "etwa {} Woche"),
+    ("about {} weeks", "etwa {} Wochen"),
+    ("about {} day", "etwa {} Tag"),
+    ("about {} days", "etwa {} Tage"),
+    ("about {} hour", "etwa {} Stunde"),
+    ("about {} hours", "etwa {} Stunden"),
+    ("about {} minute", "etwa {} Minute"),
+    ("about {} minutes", "etwa {} Minuten"),
+    ("about {} second", "etwa {} Sekunde"),
+    ("about {} seconds", "etwa {} Sekunden"),
+    // Ratios
+    ("about {} in 10", "etwa {} von 10"),
+    ("about 1 in {}", "etwa 1 von {}"),
+    ("almost all", "fast alle"),
+    ("all", "alle"),
+    ("about {} times", "etwa {}-mal"),
+    // Relative times
+    ("just now", "gerade eben"),
+    ("{} year ago", "vor {} Jahr"),
+    ("in {} year", "in {} Jahr"),
+    ("{} years ago", "vor {} Jahren"),
+    ("in {} years", "in {} Jahren"),
+    ("{} month ago", "vor {} Monat"),
+    ("in {} month", "in {} Monat"),
+    ("{} months ago", "vor {} Monaten"),
+    ("in {} months", "in {} Monaten"),
+    ("{} week ago", "vor {} Woche"),
+    ("in {} week", "in {} Woche"),
+    ("{} weeks ago", "vor {} Wochen"),
+    ("in {} weeks", "in {} Wochen"),
+    ("{} day ago", "vor {} Tag"),
+    ("in {} day", "in {} Tag"),
+    ("{} days ago", "vor {} Tagen"),
+    ("in {} days", "in {} Tagen"),
+    ("{} hour ago", "vor {} Stunde"),
+    ("in {} hour", "in {} Stunde"),
+    ("{} hours ago", "vor {} Stunden"),
+    ("in {} hours", "in {} Stunden"),
+    ("{} minute ago", "vor {} Minute"),
+    ("in {} minute", "in {} Minute"),
+    ("{} minutes ago", "vor {} Minuten"),
+    ("in {} minutes", "in {} Minuten"),
+    ("{} second ago", "vor {} Sekunde"),
+    ("in {} second", "in {} Sekunde"),
+    ("{} seconds ago", "vor {} Sekunden"),
+    ("in {} seconds", "in {} Sekunden"),
+    // Money
+    ("dollars", "Dollar"),
+    ("about {} {currency}", "etwa {} {currency}"),
+    ("about {} thousand {currency}", "etwa {} Tausend {currency}"),
+    (
+        "about {} million {currency}",
+        "etwa {} Millionen {currency}",
+    ),
+    (
+        "about {} billion {currency}",
+        "etwa {} Milliarden {currency}",
+    ),
+    (
+        "about {} trillion {currency}",
+        "etwa {} Billionen {currency}",
+    ),
+    // Comparisons
+    ("≈ {ratio} × {reference}", "≈ {ratio} × {reference}"),
+    ("a dozen", "ein Dutzend"),
+    ("the days in a year", "die Tage eines Jahres"),
+    (
+        "a football stadium crowd",
+        "das Publikum eines Fußballstadions",
+    ),
+    ("the population of London", "die Bevölkerung Londons"),
+    ("the population of Germany", "die Bevölkerung Deutschlands"),
+    ("the population of the USA", "die Bevölkerung der USA"),
+    ("the population of the world", "die Weltbevölkerung"),
+    (
+        "the neurons in a human brain",
+        "die Neuronen im menschlichen Gehirn",
+    ),
+    (
+        "the cells in a human body",
+        "die Zellen im menschlichen Körper",
+    ),
+    ("a tweet", "ein Tweet"),
+    ("a floppy disk", "eine Diskette"),
+    ("an MP3 song", "ein MP3-Song"),
+    ("a CD", "eine CD"),
+    ("a DVD", "eine DVD"),
+    ("a Blu-ray disc", "eine Blu-ray"),
+    ("a 1 TB hard drive", "eine 1-TB-Festplatte"),
+    ("a heartbeat", "ein Herzschlag"),
+    ("a pop song", "ein Popsong"),
+    ("a feature film", "ein Spielfilm"),
+    ("a day", "ein Tag"),
+    ("a year", "ein Jahr"),
+    ("a human lifetime", "ein Menschenleben"),
+    ("a cup of coffee", "eine Tasse Kaffee"),
+    ("a smartphone", "ein Smartphone"),
+    ("a new car", "ein Neuwagen"),
+    ("a house", "ein Haus"),
+    ("a blockbuster film budget", "das Budget eines Blockbusters"),
+    ("drawing a royal flush", "einen Royal Flush ziehen"),
+    (
+        "a random day being your birthday",
+        "dass ein zufälliger Tag dein Geburtstag ist",
+    ),
+    ("drawing the ace of spades", "das Pik-Ass ziehen"),
+    ("rolling a six", "eine Sechs würfeln"),
+    ("a coin landing heads", "Kopf beim Münzwurf"),
+    // Number words
+    ("minus", "minus"),
+    ("point", "Komma"),
+    ("infinity", "unendlich"),
+    ("not a number", "keine Zahl"),
+    (
+        "{mantissa} times ten to the power of {exponent}",
+        "{mantissa} mal zehn hoch {exponent}",
+    ),
+];
+
+const FRENCH: Catalog = &[
+    // Size categories
+    ("zero", "zéro"),
+    ("microscopic", "microscopique"),
+    ("tiny", "minuscule"),
+    ("small", "petit"),
+    ("medium", "moyen"),
+    ("pretty big", "assez grand"),
+    ("extremely big", "très grand"),
+    ("gigantic", "gigantesque"),
+    ("astronomical", "astronomique"),
+    ("cosmic", "cosmique"),
+    ("rounded", "arrondi"),
+    // Bytes
+    (
+        "smaller than a disk block",
+        "plus petit qu'un bloc de disque",
+    ),
+    ("fits in CPU cache", "tient dans le cache du processeur"),
+    ("fits in RAM", "tient en mémoire vive"),
+    ("fits on a disk", "tient sur un disque"),
+    ("data centre scale", "à l'échelle d'un centre de données"),
+    // Durations
+    ("less than a second", "moins d'une seconde"),
+    ("about {} year", "environ {} an"),
+    ("about {} years", "environ {} ans"),
+    ("about {} month", "environ {} mois"),
+    ("about {} months", "environ {} mois"),
+    ("about {} week", "environ {} semaine"),
+    ("about {} weeks", "environ {} semaines"),
+    ("about {} day", "environ {} jour"),
+    ("about {} days", "environ {} jours"),
+    ("about {} hour", "environ {} heure"),
+    ("about {} hours", "environ {} heures"),
+    ("about {} minute", "environ {} minute"),
+    ("about {} minutes", "environ {} minutes"),
+    ("about {} second", "environ {} seconde"),
+    ("about {} seconds", "environ {} secondes"),
+    // Ratios
+    ("about {} in 10", "environ {} sur 10"),
+    ("about 1 in {}", "environ 1 sur {}"),
+    ("almost all", "presque tous"),
+    ("all", "tous"),
+    ("about {} times", "environ {} fois"),
+    // Relative times
+    ("just now", "à l'instant"),
+    ("{} year ago", "il y a {} an"),
+    ("in {} year", "dans {} an"),
+    ("{} years ago", "il y a {} ans"),
+    ("in {} years", "dans {} ans"),
+    ("{} month ago", "il y a {} mois"),
+    ("in {} month", "dans {} mois"),
+    ("{} months ago", "il y a {} mois"),
+    ("in {} months", "dans {} mois"),
+    ("{} week ago", "il y a {} semaine"),
+    ("in {} week", "dans {} semaine"),
+    ("{} weeks ago", "il y a {} semaines"),
+    ("in {} weeks", "dans {} semaines"),
+    ("{} day ago", "il y a {} jour"),
+    ("in {} day", "dans {} jour"),
+    ("{} days ago", "il y a {} jours"),
+    ("in {} days", "dans {} jours"),
+    ("{} hour ago", "il y a {} heure"),
+    ("in {} hour", "dans {} heure"),
+    ("{} hours ago", "il y a {} heures"),
+    ("in {} hours", "dans {} heures"),
+    ("{} minute ago", "il y a {} minute"),
+    ("in {} minute", "dans {} minute"),
+    ("{} minutes ago", "il y a {} minutes"),
+    ("in {} minutes", "dans {} minutes"),
+    ("{} second ago", "il y a {} seconde"),
+    ("in {} second", "dans {} seconde"),
+    ("{} seconds ago", "il y a {} secondes"),
+    ("in {} seconds", "dans {} secondes"),
+    // Money
+    ("dollars", "dollars"),
+    ("about {} {currency}", "environ {} {currency}"),
+    (
+        "about {} thousand {currency}",
+        "environ {} mille {currency}",
+    ),
+    (
+        "about {} million {currency}",
+        "environ {} millions de {currency}",
+    ),
+    (
+        "about {} billion {currency}",
+        "environ {} milliards de {currency}",
+    ),
+    (
+        "about {} trillion {currency}",
+        "environ {} billions de {currency}",
+    ),
+    // Comparisons
+    ("≈ {ratio} × {reference}", "≈ {ratio} × {reference}"),
+    ("a dozen", "une douzaine"),
+    ("the days in a year", "les jours d'une année"),
+    (
+        "a football stadium crowd",
+        "la foule d'un stade de football",
+    ),
+    ("the population of London", "la population de Londres"),
+    ("the population of Germany", "la population de l'Allemagne"),
+    ("the population of the USA", "la population des États-Unis"),
+    ("the population of the world", "la population mondiale"),
+    (
+        "the neurons in a human brain",
+        "les neurones d'un cerveau humain",
+    ),
+    (
+        "the cells in a human body",
+        "les cellules d'un corps humain",
+    ),
+    ("a tweet", "un tweet"),
+    ("a floppy disk", "une disquette"),
+    ("an MP3 song", "une chanson MP3"),
+    ("a CD", "un CD"),
+    ("a DVD", "un DVD"),
+    ("a Blu-ray disc", "un disque Blu-ray"),
+    ("a 1 TB hard drive", "un disque dur de 1 To"),
+    ("a heartbeat", "un battement de cœur"),
+    ("a pop song", "une chanson pop"),
+    ("a feature film", "un long métrage"),
+    ("a day", "un jour"),
+    ("a year", "un an"),
+    ("a human lifetime", "une vie humaine"),
+    ("a cup of coffee", "une tasse de café"),
+    ("a smartphone", "un smartphone"),
+    ("a new car", "une voiture neuve"),
+    ("a house", "une maison"),
+    ("a blockbuster film budget", "le budget d'un film à succès"),
+    ("drawing a royal flush", "tirer une quinte flush royale"),
+    (
+        "a random day being your birthday",
+        "qu'un jour au hasard soit votre anniversaire",
+    ),
+    ("drawing the ace of spades", "tirer l'as de pique"),
+    ("rolling a six", "faire un six"),
+    ("a coin landing heads", "une pièce tombant sur face"),
+    // Number words
+    ("minus", "moins"),
+    ("point", "virgule"),
+    ("infinity", "l'infini"),
+    ("not a number", "pas un nombre"),
+    (
+        "{mantissa} times ten to the power of {exponent}",
+        "{mantissa} fois dix puissance {exponent}",
+    ),
+];
+
+const SPANISH: Catalog = &[
+    // Size categories
+    ("zero", "cero"),
+    ("microscopic", "microscópico"),
+    ("tiny", "diminuto"),
+    ("small", "pequeño"),
+    ("medium", "mediano"),
+    ("pretty big", "bastante grande"),
+    ("extremely big", "muy grande"),
+    ("gigantic", "gigantesco"),
+    ("astronomical", "astronómico"),
+    ("cosmic", "cósmico"),
+    ("rounded", "redondeado"),
+    // Bytes
+    (
+        "smaller than a disk block",
+        "más pequeño que un bloque de disco",
+    ),
+    ("fits in CPU cache", "cabe en la caché de la CPU"),
+    ("fits in RAM", "cabe en la RAM"),
+    ("fits on a disk", "cabe en un disco"),
+    ("data centre scale", "escala de centro de datos"),
+    // Durations
+    ("less than a second", "menos de un segundo"),
+    ("about {} year", "alrededor de {} año"),
+    ("about {} years", "alrededor de {} años"),
+    ("about {} month", "alrededor de {} mes"),
+    ("about {} months", "alrededor de {} meses"),
+    ("about {} week", "alrededor de {} semana"),
+    ("about {} weeks", "alrededor de {} semanas"),
+    ("about {} day", "alrededor de {} día"),
+    ("about {} days", "alrededor de {} días"),
+    ("about {} hour", "alrededor de {} hora"),
+    ("about {} hours", "alrededor de {} horas"),
+    ("about {} minute", "alrededor de {} minuto"),
+    ("about {} minutes", "alrededor de {} minutos"),
+    ("about {} second", "alrededor de {} segundo"),
+    ("about {} seconds", "alrededor de {} segundos"),
+    // Ratios
+    ("about {} in 10", "alrededor de {} de cada 10"),
+    ("about 1 in {}", "alrededor de 1 de cada {}"),
+    ("almost all", "casi todos"),
+    ("all", "todos"),
+    ("about {} times", "alrededor de {} veces"),
+    // Relative times
+    ("just now", "ahora mismo"),
+    ("{} year ago", "hace {} año"),
+    ("in {} year", "dentro de {} año"),
+    ("{} years ago", "hace {} años"),
+    ("in {} years", "dentro de {} años"),
+    ("{} month ago", "hace {} mes"),
+    ("in {} month", "dentro de {} mes"),
+    ("{} months ago", "hace {} meses"),
+    ("in {} months", "dentro de {} meses"),
+    ("{} week ago", "hace {} semana"),
+    ("in {} week", "dentro de {} semana"),
+    ("{} weeks ago", "hace {} semanas"),
+    ("in {} weeks", "dentro de {} semanas"),
+    ("{} day ago", "hace {} día"),
+    ("in {} day", "dentro de {} día"),
+    ("{} days ago", "hace {} días"),
+    ("in {} days", "dentro de {} días"),
+    ("{} hour ago", "hace {} hora"),
+    ("in {} hour", "dentro de {} hora"),
+    ("{} hours ago", "hace {} horas"),
+    ("in {} hours", "dentro de {} horas"),
+    ("{} minute ago", "hace {} minuto"),
+    ("in {} minute", "dentro de {} minuto"),
+    ("{} minutes ago", "hace {} minutos"),
+    ("in {} minutes", "dentro de {} minutos"),
+    ("{} second ago", "hace {} segundo"),
+    ("in {} second", "dentro de {} segundo"),
+    ("{} seconds ago", "hace {} segundos"),
+    ("in {} seconds", "dentro de {} segundos"),
+    // Money
+    ("dollars", "dólares"),
+    ("about {} {currency}", "alrededor de {} {currency}"),
+    (
+        "about {} thousand {currency}",
+        "alrededor de {} mil {currency}",
+    ),
+    (
+        "about {} million {currency}",
+        "alrededor de {} millones de {currency}",
+    ),
+    (
+        "about {} billion {currency}",
+        "alrededor de {} mil millones de {currency}",
+    ),
+    (
+        "about {} trillion {currency}",
+        "alrededor de {} billones de {currency}",
+    ),
+    // Comparisons
+    ("≈ {ratio} × {reference}", "≈ {ratio} × {reference}"),
+    ("a dozen", "una docena"),
+    ("the days in a year", "los días de un año"),
+    (
+        "a football stadium crowd",
+        "el público de un estadio de fútbol",
+    ),
+    ("the population of London", "la población de Londres"),
+    ("the population of Germany", "la población de Alemania"),
+    ("the population of the USA", "la población de EE. UU."),
+    ("the population of the world", "la población mundial"),
+    (
+        "the neurons in a human brain",
+        "las neuronas de un cerebro humano",
+    ),
+    (
+        "the cells in a human body",
+        "las células de un cuerpo humano",
+    ),
+    ("a tweet", "un tuit"),
+    ("a floppy disk", "un disquete"),
+    ("an MP3 song", "una canción MP3"),
+    ("a CD", "un CD"),
+    ("a DVD", "un DVD"),
+    ("a Blu-ray disc", "un disco Blu-ray"),
+    ("a 1 TB hard drive", "un disco duro de 1 TB"),
+    ("a heartbeat", "un latido"),
+    ("a pop song", "una canción pop"),
+    ("a feature film", "un largometraje"),
+    ("a day", "un día"),
+    ("a year", "un año"),
+    ("a human lifetime", "una vida humana"),
+    ("a cup of coffee", "una taza de café"),
+    ("a smartphone", "un teléfono inteligente"),
+    ("a new car", "un coche nuevo"),
+    ("a house", "una casa"),
+    (
+        "a blockbuster film budget",
+        "el presupuesto de una superproducción",
+    ),
+    ("drawing a royal flush", "sacar una escalera real"),
+    (
+        "a random day being your birthday",
+        "que un día al azar sea tu cumpleaños",
+    ),
+    ("drawing the ace of spades", "sacar el as de picas"),
+    ("rolling a six", "sacar un seis"),
+    ("a coin landing heads", "que una moneda salga cara"),
+    // Number words
+    ("minus", "menos"),
+    ("point", "coma"),
+    ("infinity", "infinito"),
+    ("not a number", "no es un número"),
+    (
+        "{mantissa} times ten to the power of {exponent}",
+        "{mantissa} por diez elevado a {exponent}",
+    ),
+];
+
+const JAPANESE: Catalog = &[
+    // Size categories
+    ("zero", "ゼロ"),
+    ("microscopic", "極小"),
+    ("tiny", "ごく小さい"),
+    ("small", "小さい"),
+    ("medium", "中くらい"),
+    ("pretty big", "かなり大きい"),
+    ("extremely big", "非常に大きい"),
+    ("gigantic", "巨大"),
+    ("astronomical", "天文学的"),
+    ("cosmic", "宇宙規模"),
+    ("rounded", "四捨五入"),
+    // Bytes
+    ("smaller than a disk block", "ディスクブロック未満"),
+    ("fits in CPU cache", "CPUキャッシュに収まる"),
+    ("fits in RAM", "RAMに収まる"),
+    ("fits on a disk", "ディスクに収まる"),
+    ("data centre scale", "データセンター規模"),
+    // Durations
+    ("less than a second", "1秒未満"),
+    ("about {} year", "約{}年"),
+    ("about {} years", "約{}年"),
+    ("about {} month", "約{}か月"),
+    ("about {} months", "約{}か月"),
+    ("about {} week", "約{}週間"),
+    ("about {} weeks", "約{}週間"),
+    ("about {} day", "約{}日"),
+    ("about {} days", "約{}日"),
+    ("about {} hour", "約{}時間"),
+    ("about {} hours", "約{}時間"),
+    ("about {} minute", "約{}分"),
+    ("about {} minutes", "約{}分"),
+    ("about {} second", "約{}秒"),
+    ("about {} seconds", "約{}秒"),
+    // Ratios
+    ("about {} in 10", "10のうち約{}"),
+    ("about 1 in {}", "約{}分の1"),
+    ("almost all", "ほぼすべて"),
+    ("all", "すべて"),
+    ("about {} times", "約{}倍"),
+    // Relative times
+    ("just now", "たった今"),
+    ("{} year ago", "{}年前"),
+    ("in {} year", "{}年後"),
+    ("{} years ago", "{}年前"),
+    ("in {} years", "{}年後"),
+    ("{} month ago", "{}か月前"),
+    ("in {} month", "{}か月後"),
+    ("{} months ago", "{}か月前"),
+    ("in {} months", "{}か月後"),
+    ("{} week ago", "{}週間前"),
+    ("in {} week", "{}週間後"),
+    ("{} weeks ago", "{}週間前"),
+    ("in {} weeks", "{}週間後"),
+    ("{} day ago", "{}日前"),
+    ("in {} day", "{}日後"),
+    ("{} days ago", "{}日前"),
+    ("in {} days", "{}日後"),
+    ("{} hour ago", "{}時間前"),
+    ("in {} hour", "{}時間後"),
+    ("{} hours ago", "{}時間前"),
+    ("in {} hours", "{}時間後"),
+    ("{} minute ago", "{}分前"),
+    ("in {} minute", "{}分後"),
+    ("{} minutes ago", "{}分前"),
+    ("in {} minutes", "{}分後"),
+    ("{} second ago", "{}秒前"),
+    ("in {} second", "{}秒後"),
+    ("{} seconds ago", "{}秒前"),
+    ("in {} seconds", "{}秒後"),
+    // Money
+    ("dollars", "ドル"),
+    ("about {} {currency}", "約{}{currency}"),
+    ("about {} thousand {currency}", "約{}千{currency}"),
+    ("about {} million {currency}", "約{}百万{currency}"),
+    ("about {} billion {currency}", "約{}十億{currency}"),
+    ("about {} trillion {currency}", "約{}兆{currency}"),
+    // Comparisons
+    ("≈ {ratio} × {reference}", "≈ {reference}の{ratio}倍"),
+    ("a dozen", "1ダース"),
+    ("the days in a year", "1年の日数"),
+    ("a football stadium crowd", "サッカースタジアムの観客"),
+    ("the population of London", "ロンドンの人口"),
+    ("the population of Germany", "ドイツの人口"),
+    ("the population of the USA", "アメリカの人口"),
+    ("the population of the world", "世界の人口"),
+    ("the neurons in a human brain", "人間の脳のニューロン"),
+    ("the cells in a human body", "人体の細胞"),
+    ("a tweet", "ツイート"),
+    ("a floppy disk", "フロッピーディスク"),
+    ("an MP3 song", "MP3の曲"),
+    ("a CD", "CD"),
+    ("a DVD", "DVD"),
+    ("a Blu-ray disc", "ブルーレイディスク"),
+    ("a 1 TB hard drive", "1TBのハードディスク"),
+    ("a heartbeat", "心拍"),
+    ("a pop song", "ポップソング"),
+    ("a feature film", "長編映画"),
+    ("a day", "1日"),
+    ("a year", "1年"),
+    ("a human lifetime", "人の一生"),
+    ("a cup of coffee", "コーヒー1杯"),
+    ("a smartphone", "スマートフォン"),
+    ("a new car", "新車"),
+    ("a house", "家"),
+    ("a blockbuster film budget", "大作映画の予算"),
+    ("drawing a royal flush", "ロイヤルフラッシュを引く確率"),
+    (
+        "a random day being your birthday",
+        "ランダムな日が誕生日である確率",
+    ),
+    ("drawing the ace of spades", "スペードのエースを引く確率"),
+    ("rolling a six", "サイコロで6が出る確率"),
+    ("a coin landing heads", "コインが表になる確率"),
+    // Number words
+    ("minus", "マイナス"),
+    ("point", "点"),
+    ("infinity", "無限大"),
+    ("not a number", "非数"),
+    (
+        "{mantissa} times ten to the power of {exponent}",
+        "{mantissa}かける十の{exponent}乗",
+    ),
+];
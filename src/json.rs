@@ -0,0 +1,108 @@
+//! Structured output for --json. Each input produces one JSON object on
+//! a single line, so streams are newline-delimited JSON.
+
+use crate::{NUMBER_BYTES_RE, Options, categories, grouping::Grouping, parse};
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Number(NumberRecord),
+    Text(TextRecord),
+}
+
+#[derive(Serialize)]
+struct NumberRecord {
+    input: String,
+    value: f64,
+    /// Always grouped in threes, so output doesn't depend on the locale.
+    grouped: String,
+    rounded: bool,
+    /// The rounded value minus the original value.
+    rounding_delta: f64,
+    /// A stable machine-readable name, e.g. `pretty_big`.
+    category: Option<String>,
+    bytes: String,
+}
+
+#[derive(Serialize)]
+struct TextRecord {
+    input: String,
+    output: String,
+    numbers: Vec<Span>,
+}
+
+/// A number found in a line of text.
+#[derive(Serialize)]
+struct Span {
+    /// Byte offsets of the number in the input.
+    start: usize,
+    end: usize,
+    text: String,
+    value: f64,
+    replacement: String,
+}
+
+/// Describe a line of input (excluding its terminator) as a JSON object.
+pub fn line_to_json(line: &[u8], options: &Options) -> String {
+    let record = match std::str::from_utf8(line) {
+        Ok(text) => match parse::parse_number(text) {
+            Some(number) => Record::Number(number_record(text, number, options)),
+            None => Record::Text(text_record(line)),
+        },
+        Err(_) => Record::Text(text_record(line)),
+    };
+
+    serde_json::to_string(&record).expect("records should always serialize")
+}
+
+fn number_record(input: &str, number: f64, options: &Options) -> NumberRecord {
+    let rounded = crate::round_to_hundredths(number);
+    let was_rounded = (number - rounded).abs() > f64::EPSILON;
+
+    NumberRecord {
+        input: input.to_string(),
+        value: number,
+        grouped: crate::format_number_with_separators(rounded, Grouping::Western),
+        rounded: was_rounded,
+        rounding_delta: if was_rounded { rounded - number } else { 0.0 },
+        category: categories::category_name(number, &options.categories),
+        bytes: crate::format_as_binary_units(rounded),
+    }
+}
+
+/// Describe a line of text, which may not be valid UTF-8. Offsets are
+/// always byte offsets into the original line.
+fn text_record(line: &[u8]) -> TextRecord {
+    let mut numbers = vec![];
+    let mut output = Vec::with_capacity(line.len());
+    let mut last_end = 0;
+
+    for found in NUMBER_BYTES_RE.find_iter(line) {
+        // Matches never include invalid UTF-8.
+        let text = std::str::from_utf8(found.as_bytes()).unwrap_or_default();
+        let Some(value) = parse::parse_number_in_text(text) else {
+            continue;
+        };
+        let replacement = crate::format_single_number(value, Grouping::Western);
+
+        output.extend_from_slice(&line[last_end..found.start()]);
+        output.extend_from_slice(replacement.as_bytes());
+        last_end = found.end();
+
+        numbers.push(Span {
+            start: found.start(),
+            end: found.end(),
+            text: text.to_string(),
+            value,
+            replacement,
+        });
+    }
+    output.extend_from_slice(&line[last_end..]);
+
+    TextRecord {
+        input: String::from_utf8_lossy(line).into_owned(),
+        output: String::from_utf8_lossy(&output).into_owned(),
+        numbers,
+    }
+}
@@ -0,0 +1,156 @@
+//! Descriptions for numbers that represent a particular kind of
+//! quantity, such as a number of bytes or seconds.
+
+use crate::currency::Currency;
+use crate::i18n::{self, Lang};
+use clap::ValueEnum;
+
+/// What a number represents, which determines how it's described.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// A plain number, described using the size categories
+    Number,
+    /// A number of bytes, described relative to storage sizes
+    Bytes,
+    /// A number of seconds, described in the most natural unit
+    Duration,
+    /// An amount of money, described in words
+    Money,
+    /// A fraction, described as e.g. "about 1 in 8"
+    Ratio,
+}
+
+const KIB: f64 = 1024.0;
+const MIB: f64 = KIB * 1024.0;
+const GIB: f64 = MIB * 1024.0;
+const TIB: f64 = GIB * 1024.0;
+
+/// A description of `number` as a `kind` of quantity in `lang`, or
+/// `None` for plain numbers and for infinity and NaN. Money is in
+/// `currency`, or dollars if it's not given.
+pub fn describe(
+    kind: Kind,
+    number: f64,
+    currency: Option<&Currency>,
+    lang: Lang,
+) -> Option<String> {
+    if !number.is_finite() {
+        return None;
+    }
+
+    let abs_value = number.abs();
+    match kind {
+        Kind::Number => None,
+        Kind::Bytes => Some(i18n::translate(lang, describe_bytes(abs_value)).to_string()),
+        Kind::Duration => Some(describe_duration(abs_value, lang)),
+        Kind::Money => Some(describe_money(abs_value, currency, lang)),
+        Kind::Ratio => Some(describe_ratio(abs_value, lang)),
+    }
+}
+
+fn describe_bytes(bytes: f64) -> &'static str {
+    if bytes < 4.0 * KIB {
+        "smaller than a disk block"
+    } else if bytes < 32.0 * MIB {
+        "fits in CPU cache"
+    } else if bytes < 16.0 * GIB {
+        "fits in RAM"
+    } else if bytes < 16.0 * TIB {
+        "fits on a disk"
+    } else {
+        "data centre scale"
+    }
+}
+
+/// Lengths of time, in seconds.
+pub const MINUTE: f64 = 60.0;
+pub const HOUR: f64 = 60.0 * MINUTE;
+pub const DAY: f64 = 24.0 * HOUR;
+pub const WEEK: f64 = 7.0 * DAY;
+pub const YEAR: f64 = 365.25 * DAY;
+pub const MONTH: f64 = YEAR / 12.0;
+
+fn describe_duration(seconds: f64, lang: Lang) -> String {
+    if seconds < 1.0 {
+        return i18n::translate(lang, "less than a second").to_string();
+    }
+
+    let units = [
+        (YEAR, "year"),
+        (MONTH, "month"),
+        (WEEK, "week"),
+        (DAY, "day"),
+        (HOUR, "hour"),
+        (MINUTE, "minute"),
+        (1.0, "second"),
+    ];
+    let (size, name) = units
+        .iter()
+        .find(|(size, _)| seconds >= *size)
+        .copied()
+        .unwrap_or((1.0, "second"));
+
+    let count = format_approximate(seconds / size);
+    let plural = if count == "1" { "" } else { "s" };
+    let message = format!("about {{}} {}{}", name, plural);
+    i18n::translate_with(lang, &message, &count)
+}
+
+fn describe_money(amount: f64, currency: Option<&Currency>, lang: Lang) -> String {
+    let scales = [
+        (1e12, " trillion"),
+        (1e9, " billion"),
+        (1e6, " million"),
+        (1e3, " thousand"),
+    ];
+    let (size, name) = scales
+        .iter()
+        .find(|(size, _)| amount >= *size)
+        .copied()
+        .unwrap_or((1.0, ""));
+
+    // Only dollars are named; other currencies go by their code.
+    let currency = match currency {
+        Some(currency) if currency.code != "USD" => currency.code,
+        _ => i18n::translate(lang, "dollars"),
+    };
+    let message = format!("about {{}}{} {{currency}}", name);
+    i18n::translate_with(lang, &message, &format_approximate(amount / size))
+        .replace("{currency}", currency)
+}
+
+fn describe_ratio(fraction: f64, lang: Lang) -> String {
+    use num_format::{Locale, ToFormattedString};
+
+    if fraction > 0.5 && fraction < 1.0 {
+        let tenths = (fraction * 10.0).round();
+        if tenths >= 10.0 {
+            return i18n::translate(lang, "almost all").to_string();
+        }
+        return i18n::translate_with(lang, "about {} in 10", &tenths.to_string());
+    }
+    if fraction == 1.0 {
+        return i18n::translate(lang, "all").to_string();
+    }
+    if fraction > 1.0 {
+        return i18n::translate_with(lang, "about {} times", &format_approximate(fraction));
+    }
+
+    let one_in = (1.0 / fraction).round() as u64;
+    i18n::translate_with(
+        lang,
+        "about 1 in {}",
+        &one_in.to_formatted_string(&Locale::en),
+    )
+}
+
+/// Format `value` with at most one decimal place, omitting it if it's
+/// zero: 1.25 becomes "1.3", 3.02 becomes "3".
+fn format_approximate(value: f64) -> String {
+    let rounded = (value * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{:.0}", rounded)
+    } else {
+        format!("{:.1}", rounded)
+    }
+}
@@ -1,20 +1,64 @@
-use clap::Parser;
+mod categories;
+mod compare;
+mod currency;
+mod digits;
+mod duration;
+mod grouping;
+mod i18n;
+mod json;
+mod kinds;
+mod notation;
+mod parse;
+mod porcelain;
+mod ratio;
+mod si;
+mod template;
+mod timestamp;
+mod words;
+
+use categories::{Category, Style};
+use clap::{ArgGroup, Parser, ValueEnum};
 use colored::Colorize;
+use compare::Reference;
+use currency::{Currency, CurrencyFormat};
+use digits::DigitScript;
+use duration::{DurationFormat, TimeUnit};
+use grouping::Grouping;
 use humansize::{BINARY, format_size};
-use num_format::{Locale, ToFormattedString};
+use i18n::Lang;
+use kinds::Kind;
+use notation::{ExponentStyle, Notation, NotationFormat};
+use porcelain::PorcelainVersion;
+use ratio::{RatioFormat, RatioUnit};
 use regex::Regex;
-use std::io::{self, BufRead};
+use si::SiUnit;
+use std::env;
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::LazyLock;
+use std::thread;
+use std::time::Duration;
+use template::{Field, Template};
+use timestamp::TimestampFormat;
+use words::Scale;
 
 /// Format numbers with thousand separators and colorful size descriptions
 #[derive(Parser)]
 #[command(name = "nn")]
+#[command(group(
+    ArgGroup::new("decimals").args(["duration", "percent", "per_mille", "basis_points"])
+))]
 #[command(version)]
 #[command(about)]
 #[command(
     long_about = "Format numbers with thousand separators and colorful size descriptions
 
 Reads a number from the command line or stdin and outputs it formatted with
-commas as thousand separators, along with a description of its size. Supports
+commas as thousand separators, along with a description of its size, from
+(microscopic) for values below 0.001 up to (cosmic) for values of 10^18 and
+beyond. Use --magnitude to include the order of magnitude too. Supports
 integers, decimals, and scientific notation. Decimal numbers are rounded to 2
 decimal places, with a \"(rounded)\" note when applicable.
 
@@ -24,6 +68,109 @@ numbers while preserving the rest of the text unchanged.
 Use --bytes flag to also display the number in binary units (KiB, MiB, GiB, etc.)
 using 1024-based prefixes.
 
+Use --kind to say what the number represents, so it's described
+appropriately: bytes are described relative to storage sizes (the default
+with --bytes), durations in seconds as e.g. \"about 3 days\", and money (the
+default with --currency) as e.g. \"about 1.2 million dollars\".
+
+Numbers can also be written in words (\"two point five million\", \"a
+dozen\") or with a scale (\"2.5 million\", \"1.2bn\"), and are converted to
+digits. A single number can also use a short suffix like 3k or 5m.
+
+Use --follow to process a file and then keep processing lines as they're
+appended, like `tail -F`. Log rotation and truncation are handled by
+reopening the file, and a file that doesn't exist yet is waited for.
+
+Colors are used when writing to a terminal. Use --color to override this;
+the NO_COLOR and CLICOLOR_FORCE environment variables are also respected.
+
+The size descriptions can be customised with --category MIN:LABEL[:STYLE],
+which applies LABEL to numbers whose magnitude is at least MIN. STYLE is
+a color name, optionally combined with bold or dimmed using +, e.g.
+bright_red+bold. Categories can also be read from a file with one
+category per line, given with --category-file or placed in
+~/.config/nn/categories.
+
+Use --compare to relate the number to a familiar quantity of the same kind,
+such as the population of a country or the capacity of a DVD. Add your own
+quantities with --reference VALUE:NAME, or in ~/.config/nn/references with
+one KIND:VALUE:NAME per line.
+
+Use --format to choose exactly what's shown for each number. The template
+can include {value} (the number as given), {grouped} (with separators),
+{compact} (e.g. 1.2M), {bytes} (e.g. 1.50 KiB), {desc} (the size
+description), {rounded} (\"(rounded)\" if rounding occurred), {sci} (e.g.
+1.23e6) and {words} (spelled out in words). Use {{ and }} for literal
+braces, and \\t and \\n for tabs and newlines.
+
+Use --json to output a JSON object for each input instead, giving the value,
+its formatting and its size category. For text, the object lists each number
+found with its byte offsets and replacement. Input from stdin produces one
+object per line (NDJSON).
+
+Use --porcelain for tab-separated output that won't change between
+releases. Each input produces one row of VALUE, GROUPED, CATEGORY, ROUNDED
+(1 or 0) and BYTES, with all columns empty for input that isn't a number.
+--porcelain is currently --porcelain=v1.
+
+Use --words to spell numbers out, both for single numbers and numbers
+within text. Large English numbers use the short scale (a billion is 10^9)
+unless --scale long is given.
+
+Use --grouping indian to group digits in the Indian style, e.g.
+12,34,56,789, with lakhs and crores in {compact} and words. This is the
+default for the en_IN and hi_IN locales. --grouping myriad groups digits
+in fours with Chinese and Japanese units, e.g. 1億2345万6789, and
+--grouping myriad-hangul uses Korean units, e.g. 1억 2345만 6789. JSON and
+porcelain output always group digits in threes.
+
+Use --duration UNIT to show numbers of seconds (s), milliseconds (ms),
+microseconds (us) or nanoseconds (ns) as durations such as 1d 2h 3m 4s, or
+1.5 ms for durations under a second. --largest-unit and --smallest-unit
+limit the units used, and --precision sets the decimal places of the
+smallest unit. With --duration, input like 1h30m is understood too, and
+converted to UNIT. In text, only numbers followed by a unit of time, such as
+1500 ms or 90 seconds, are shown as durations.
+
+Use --percent, --per-mille or --basis-points to show fractions as e.g.
+5.23%, 52.3‰ or 523 bp, with up to --precision decimal places. Ratios are
+described as e.g. \"about 1 in 8\". Input such as 12.5%, 5‰ or 25bp is
+understood as a fraction, and shown the same way.
+
+Use --timestamps to show Unix timestamps from 2000 to 2100, in seconds,
+milliseconds, microseconds or nanoseconds, as ISO-8601 dates along with how
+long ago they were. Dates are in UTC unless --utc-offset is given, and
+--now sets the time they're relative to.
+
+Use --currency CODE to show numbers as amounts of money in that currency,
+e.g. USD, EUR or JPY, rounded to its minor units: none for JPY, three for
+BHD. The symbol goes before or after the amount as usual for --lang, and
+--accounting shows negative amounts in parentheses. In text, amounts
+written with a currency symbol such as $1234.5 or €1234 are formatted in
+that currency. Amounts are described in dollars, or by the currency's code.
+
+Use --si-unit UNIT to show quantities with SI prefixes, from y (10^-24) to
+Y (10^24), e.g. --si-unit s shows 0.0000125 as 12.5 µs. In text, only
+numbers followed by the unit are converted, and prefixed units like 125 ms
+are understood.
+
+Use --sci or --eng to show numbers in scientific or engineering notation,
+where the exponent is a multiple of three. --sig-figs sets the number of
+significant figures (3 by default), and --exponent-style chooses between
+1.23e5 (e), 1.23×10^5 (times, the default) and 1.23×10⁵ (superscript).
+
+Numbers written with Arabic-Indic, Eastern Arabic, Devanagari, Bengali,
+Thai or full-width digits are recognised too. Use --digits to write output
+in one of these scripts, or --digits locale for the digits of your locale.
+
+Descriptions and number words can be shown in German (de), French (fr),
+Spanish (es) or Japanese (ja) with --lang. By default the language comes
+from the LC_ALL, LC_MESSAGES or LANG environment variables, falling back
+to English. JSON and porcelain output always use English category names.
+
+Use -z to separate records with NUL rather than newline, for use with
+`find -print0` and `xargs -0`.
+
 EXAMPLES:
   nn 42                        # 42 (small)
   nn 5000                      # 5,000 (medium)
@@ -31,10 +178,41 @@ EXAMPLES:
   nn 1234567.89                # 1,234,567.89 (pretty big)
   nn 9876543210                # 9,876,543,210 (extremely big)
   nn 1.23e5                    # 123,000 (medium)
+  nn \"two point five million\"  # 2,500,000 (pretty big)
+  nn 1.2bn                     # 1,200,000,000 (extremely big)
+  nn 0.25                      # 0.25 (tiny)
+  nn 4.2e13                    # 42,000,000,000,000 (gigantic)
+  nn -m 4.2e13                 # 42,000,000,000,000 (gigantic, ~10^13)
   nn -- -5000                  # -5,000 (medium) [use -- for negative numbers]
-  nn 1048576 --bytes           # Also shows: 1 MiB
+  nn 1048576 --bytes           # 1 MiB (fits in CPU cache)
+  nn --kind duration 259200    # 259,200 (about 3 days)
+  nn --kind money 1234567      # 1,234,567 (about 1.2 million dollars)
+  nn --compare 80000000        # 80,000,000 (pretty big) ≈ the population of Germany
+  nn -b --compare 14e9         # 13.04 GiB (fits in RAM) ≈ 3 × a DVD
+  nn -f '{compact} ({desc})' 1234567  # 1.2M (pretty big)
+  nn --words 1234567           # one million two hundred thirty-four thousand ...
+  nn --lang de 5000000         # 5,000,000 (ziemlich groß)
+  nn --grouping indian 123456789  # 12,34,56,789 (pretty big)
+  nn --grouping myriad 123456789  # 1億2345万6789 (pretty big)
+  nn --duration s 93784        # 1d 2h 3m 4s (about 1.1 days)
+  nn --duration ms 1.5         # 1.5 ms (less than a second)
+  nn --duration s 1h30m        # 1h 30m (about 1.5 hours)
+  nn --percent 0.0523          # 5.23% (about 1 in 19)
+  nn 12.5%                     # 12.5% (about 1 in 8)
+  nn --timestamps --now 2023-11-17T22:13:20Z 1700000000  # 2023-11-14T22:13:20Z (3 days ago)
+  nn --currency JPY 1234.5     # ¥1,235 (rounded) (about 1.2 thousand JPY)
+  nn --si-unit Hz 2400000000   # 2.4 GHz (extremely big)
+  nn --sci 123456              # 1.23×10^5 (medium)
+  nn --eng --sig-figs 4 123456 # 123.5×10^3 (medium)
+  nn ١٢٣٤٥                     # 12,345 (medium)
+  nn --digits devanagari 12345 # १२,३४५ (medium)
+  nn --json 42.123             # {\"type\":\"number\",\"input\":\"42.123\",...}
   echo \"The file is 1024 bytes\" | nn  # The file is 1,024 bytes
-  echo \"42\" | nn              # Can also read from stdin"
+  echo \"42\" | nn              # Can also read from stdin
+  nn --follow app.log          # Process app.log and follow new lines
+  tail -f app.log | nn --line-buffered  # Flush after every line
+  find . -print0 | nn -z | xargs -0 ls  # NUL-separated records
+  nn --category 0:few --category 100:lots:red 250  # 250 (lots)"
 )]
 struct Cli {
     /// The number to format (reads from stdin if not provided)
@@ -43,29 +221,496 @@ struct Cli {
     /// Display the number in binary units (KiB, MiB, GiB, etc.)
     #[arg(short, long)]
     bytes: bool,
+
+    /// Process FILE, then wait for and process lines appended to it
+    #[arg(long, value_name = "FILE", conflicts_with = "number")]
+    follow: Option<PathBuf>,
+
+    /// Flush output after every line, rather than when input is idle
+    #[arg(long)]
+    line_buffered: bool,
+
+    /// Input and output records are separated by NUL rather than newline
+    #[arg(short, long)]
+    zero_terminated: bool,
+
+    /// When to use colors
+    #[arg(long, value_name = "WHEN", value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Describe numbers of at least MIN as LABEL (can be repeated)
+    #[arg(
+        long = "category",
+        value_name = "MIN:LABEL[:STYLE]",
+        value_parser = categories::parse_category
+    )]
+    categories: Vec<Category>,
+
+    /// Read size categories from FILE, one MIN:LABEL[:STYLE] per line
+    #[arg(long, value_name = "FILE", conflicts_with = "categories")]
+    category_file: Option<PathBuf>,
+
+    /// Include the order of magnitude in the description, e.g. ~10^12
+    #[arg(short, long)]
+    magnitude: bool,
+
+    /// What the number represents, which changes how it's described
+    /// [default: number, bytes with --bytes, duration with --duration, or
+    /// ratio with --percent, --per-mille or --basis-points]
+    #[arg(short, long, value_enum)]
+    kind: Option<Kind>,
+
+    /// Compare the number with a familiar quantity, e.g. ≈ 3 × a DVD
+    #[arg(short, long)]
+    compare: bool,
+
+    /// Add a quantity to compare against with --compare (can be repeated)
+    #[arg(long = "reference", value_name = "VALUE:NAME", value_parser = compare::parse_reference, requires = "compare")]
+    references: Vec<Reference>,
+
+    /// Output numbers using TEMPLATE, e.g. '{grouped} {desc}'
+    #[arg(short, long, value_name = "TEMPLATE", value_parser = template::parse_template)]
+    format: Option<Template>,
+
+    /// Output a JSON object for each input, one per line
+    #[arg(long, conflicts_with = "format")]
+    json: bool,
+
+    /// Output tab-separated columns in a format that won't change:
+    /// value, grouped, category, rounded (1 or 0) and bytes
+    #[arg(
+        long,
+        value_name = "VERSION",
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "v1",
+        require_equals = true,
+        conflicts_with_all = ["format", "json"]
+    )]
+    porcelain: Option<PorcelainVersion>,
+
+    /// Spell numbers out in words, e.g. forty-two
+    #[arg(short, long, conflicts_with_all = ["format", "json", "porcelain"])]
+    words: bool,
+
+    /// How to name large numbers in words [default: short, or indian
+    /// with Indian grouping]
+    #[arg(long, value_name = "SCALE", value_enum)]
+    scale: Option<Scale>,
+
+    /// Show numbers as durations in UNIT, e.g. 1d 2h 3m 4s or 1.5 ms
+    #[arg(
+        long,
+        value_name = "UNIT",
+        value_enum,
+        conflicts_with_all = ["bytes", "format", "json", "porcelain", "words", "si_unit", "sci", "eng"]
+    )]
+    duration: Option<TimeUnit>,
+
+    /// The largest unit for --duration
+    #[arg(long, value_name = "UNIT", value_enum, default_value_t = TimeUnit::D, requires = "duration")]
+    largest_unit: TimeUnit,
+
+    /// The smallest unit for --duration [default: s, or a single unit for
+    /// durations under a second]
+    #[arg(long, value_name = "UNIT", value_enum, requires = "duration")]
+    smallest_unit: Option<TimeUnit>,
+
+    /// The number of decimal places for the smallest unit of --duration,
+    /// or for --percent, --per-mille and --basis-points
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        value_parser = clap::value_parser!(u8).range(0..=6),
+        requires = "decimals"
+    )]
+    precision: u8,
+
+    /// Show fractions as percentages, e.g. 5.23%
+    #[arg(
+        long,
+        conflicts_with_all = ["bytes", "format", "json", "porcelain", "words", "si_unit", "sci", "eng"]
+    )]
+    percent: bool,
+
+    /// Show fractions as per-mille, e.g. 52.3‰
+    #[arg(
+        long,
+        conflicts_with_all = ["bytes", "format", "json", "porcelain", "words", "si_unit", "sci", "eng"]
+    )]
+    per_mille: bool,
+
+    /// Show fractions as basis points, e.g. 523 bp
+    #[arg(
+        long,
+        conflicts_with_all = ["bytes", "format", "json", "porcelain", "words", "si_unit", "sci", "eng"]
+    )]
+    basis_points: bool,
+
+    /// Show plausible Unix timestamps (in seconds, milliseconds,
+    /// microseconds or nanoseconds) as ISO-8601 dates
+    #[arg(long, conflicts_with_all = ["format", "json", "porcelain"])]
+    timestamps: bool,
+
+    /// The UTC offset for --timestamps, e.g. +05:30 [default: Z]
+    #[arg(
+        long,
+        value_name = "OFFSET",
+        value_parser = timestamp::parse_utc_offset,
+        allow_hyphen_values = true,
+        requires = "timestamps"
+    )]
+    utc_offset: Option<i32>,
+
+    /// The time that --timestamps are relative to, as a Unix timestamp
+    /// or an ISO-8601 date [default: the current time]
+    #[arg(long, value_name = "TIME", value_parser = timestamp::parse_now, requires = "timestamps")]
+    now: Option<i128>,
+
+    /// Show numbers as amounts of CURRENCY, e.g. USD, EUR or JPY, rounded
+    /// to its minor units
+    #[arg(
+        long,
+        value_name = "CURRENCY",
+        value_parser = currency::parse_currency,
+        conflicts_with_all = [
+            "bytes", "format", "json", "porcelain", "words", "si_unit", "sci", "eng",
+            "duration", "percent", "per_mille", "basis_points"
+        ]
+    )]
+    currency: Option<&'static Currency>,
+
+    /// Show negative amounts in parentheses with --currency, e.g. ($5.00)
+    #[arg(long, requires = "currency")]
+    accounting: bool,
+
+    /// Show numbers of UNIT with SI prefixes, e.g. 12.5 µs or 2.4 GHz
+    #[arg(
+        long,
+        value_name = "UNIT",
+        conflicts_with_all = ["bytes", "format", "json", "porcelain", "words"]
+    )]
+    si_unit: Option<String>,
+
+    /// Show numbers in scientific notation, e.g. 1.23×10^5
+    #[arg(long, conflicts_with_all = ["bytes", "format", "json", "porcelain", "words", "si_unit"])]
+    sci: bool,
+
+    /// Show numbers in engineering notation, where the exponent is a
+    /// multiple of three, e.g. 123×10^3
+    #[arg(long, conflicts_with_all = ["sci", "bytes", "format", "json", "porcelain", "words", "si_unit"])]
+    eng: bool,
+
+    /// The number of significant figures for --sci and --eng
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u8).range(1..=17)
+    )]
+    sig_figs: u8,
+
+    /// How to write exponents for --sci and --eng: 1.23e5, 1.23×10^5 or
+    /// 1.23×10⁵
+    #[arg(long, value_name = "STYLE", value_enum, default_value_t = ExponentStyle::Times)]
+    exponent_style: ExponentStyle,
+
+    /// Write output digits in SCRIPT, e.g. devanagari, or the script of
+    /// your locale with 'locale'
+    #[arg(long, value_name = "SCRIPT", value_enum, default_value_t = DigitScript::Latin)]
+    digits: DigitScript,
+
+    /// How to group digits [default: indian for the en_IN and hi_IN
+    /// locales, otherwise western]
+    #[arg(long, value_name = "STYLE", value_enum)]
+    grouping: Option<Grouping>,
+
+    /// The language for descriptions and number words [default: from
+    /// LC_ALL, LC_MESSAGES or LANG, otherwise English]
+    #[arg(long, value_name = "LANG", value_enum)]
+    lang: Option<Lang>,
 }
 
-fn get_size_description(number: f64) -> String {
-    let abs_value = number.abs();
+/// Settings derived from the command line, used when processing input.
+#[derive(Clone)]
+struct Options {
+    show_bytes: bool,
+    line_buffered: bool,
+    delimiter: u8,
+    /// Sorted by threshold.
+    categories: Vec<Category>,
+    show_magnitude: bool,
+    kind: Kind,
+    /// Quantities to compare numbers with, if --compare was given.
+    references: Option<Vec<Reference>>,
+    format: Option<Template>,
+    json: bool,
+    porcelain: Option<PorcelainVersion>,
+    words: bool,
+    scale: Scale,
+    lang: Lang,
+    grouping: Grouping,
+    digits: DigitScript,
+    /// Set by --sci or --eng.
+    notation: Option<NotationFormat>,
+    si: Option<SiUnit>,
+    duration: Option<DurationFormat>,
+    timestamps: Option<TimestampFormat>,
+    /// Set by --percent, --per-mille or --basis-points.
+    ratio: Option<RatioFormat>,
+    currency: Option<CurrencyFormat>,
+}
 
+impl Options {
+    fn from_cli(cli: &Cli) -> io::Result<Self> {
+        let mut categories = if !cli.categories.is_empty() {
+            cli.categories.clone()
+        } else if let Some(path) = &cli.category_file {
+            categories::load_category_file(path)?
+        } else {
+            match config_file("categories") {
+                Some(path) if path.exists() => categories::load_category_file(&path)?,
+                _ => categories::default_categories(),
+            }
+        };
+        categories::sort_categories(&mut categories);
+
+        let ratio = match (cli.percent, cli.per_mille, cli.basis_points) {
+            (true, _, _) => Some(RatioUnit::Percent),
+            (_, true, _) => Some(RatioUnit::PerMille),
+            (_, _, true) => Some(RatioUnit::BasisPoints),
+            _ => None,
+        }
+        .map(|unit| RatioFormat {
+            unit,
+            precision: cli.precision.into(),
+        });
+
+        let kind = cli.kind.unwrap_or(if cli.bytes {
+            Kind::Bytes
+        } else if cli.duration.is_some() {
+            Kind::Duration
+        } else if ratio.is_some() {
+            Kind::Ratio
+        } else if cli.currency.is_some() {
+            Kind::Money
+        } else {
+            Kind::Number
+        });
+
+        let references = if cli.compare {
+            // The built-in money references are in dollars, so other
+            // currencies only have the user's own.
+            let in_dollars = cli.currency.is_none_or(|currency| currency.code == "USD");
+            let mut references = if kind == Kind::Money && !in_dollars {
+                Vec::new()
+            } else {
+                compare::default_references(kind)
+            };
+            if let Some(path) = config_file("references").filter(|path| path.exists()) {
+                references.extend(compare::load_reference_file(&path, kind)?);
+            }
+            references.extend(cli.references.iter().cloned());
+            Some(references)
+        } else {
+            None
+        };
+
+        let grouping = cli.grouping.unwrap_or_else(Grouping::from_env);
+        let scale = cli.scale.unwrap_or(match grouping {
+            Grouping::Indian => Scale::Indian,
+            Grouping::Western | Grouping::Myriad | Grouping::MyriadHangul => Scale::Short,
+        });
+
+        let notation = match (cli.sci, cli.eng) {
+            (true, _) => Some(Notation::Scientific),
+            (_, true) => Some(Notation::Engineering),
+            _ => None,
+        }
+        .map(|notation| NotationFormat {
+            notation,
+            sig_figs: cli.sig_figs.into(),
+            style: cli.exponent_style,
+        });
+
+        let si = match cli.si_unit.as_deref().map(str::trim) {
+            Some("") => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--si-unit can't be empty",
+                ));
+            }
+            Some(unit) => Some(SiUnit::new(unit, &TEXT_NUMBER_PATTERN).map_err(io::Error::other)?),
+            None => None,
+        };
+
+        Ok(Options {
+            show_bytes: cli.bytes,
+            line_buffered: cli.line_buffered,
+            delimiter: if cli.zero_terminated { b'\0' } else { b'\n' },
+            categories,
+            show_magnitude: cli.magnitude,
+            kind,
+            references,
+            format: cli.format.clone(),
+            json: cli.json,
+            porcelain: cli.porcelain,
+            words: cli.words,
+            scale,
+            lang: cli.lang.unwrap_or_else(Lang::from_env),
+            grouping,
+            digits: cli.digits.resolve(),
+            notation,
+            si,
+            duration: cli.duration.map(|unit| DurationFormat {
+                unit,
+                largest: cli.largest_unit,
+                smallest: cli.smallest_unit,
+                precision: cli.precision.into(),
+            }),
+            timestamps: cli.timestamps.then(|| TimestampFormat {
+                offset_minutes: cli.utc_offset.unwrap_or(0),
+                now: cli.now.unwrap_or_else(timestamp::current_time),
+            }),
+            ratio,
+            currency: cli.currency.map(|currency| CurrencyFormat {
+                currency,
+                accounting: cli.accounting,
+            }),
+        })
+    }
+}
+
+/// The path of config file `name`: `$XDG_CONFIG_HOME/nn/NAME`, or
+/// `~/.config/nn/NAME`.
+fn config_file(name: &str) -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("nn").join(name))
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorChoice {
+    /// Use colors if stdout is a terminal, unless overridden by the
+    /// environment
+    Auto,
+    Always,
+    Never,
+}
+
+/// Is environment variable `name` set to a non-empty value?
+fn env_is_set(name: &str) -> bool {
+    env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// Should output written to stdout be colored? Only stdout is ever
+/// colored, so this doesn't depend on where stderr goes.
+///
+/// In auto mode NO_COLOR (https://no-color.org) takes priority, then
+/// CLICOLOR_FORCE and CLICOLOR (https://bixense.com/clicolors/).
+fn should_color_stdout(choice: ColorChoice) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if env_is_set("NO_COLOR") {
+                false
+            } else if env::var_os("CLICOLOR_FORCE")
+                .is_some_and(|value| !value.is_empty() && value != "0")
+            {
+                true
+            } else if env::var_os("CLICOLOR").is_some_and(|value| value == "0") {
+                false
+            } else {
+                io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+fn get_size_description(number: f64, options: &Options) -> String {
+    match describe_size(number, options) {
+        Some((description, style)) => style.apply(&format!("({})", description)).to_string(),
+        None => String::new(),
+    }
+}
+
+/// The size description of `number` (without parentheses) and the style
+/// to show it in.
+fn describe_size(number: f64, options: &Options) -> Option<(String, Style)> {
     // Special case for zero
-    if abs_value == 0.0 {
-        return "(zero)".bright_white().bold().to_string();
+    if number == 0.0 {
+        let style = Style {
+            color: Some(colored::Color::BrightWhite),
+            bold: true,
+            dimmed: false,
+        };
+        return Some((i18n::translate(options.lang, "zero").to_string(), style));
     }
 
-    match abs_value as i64 {
-        0..=999 => "(small)".bright_cyan().to_string(),
-        1_000..=999_999 => "(medium)".bright_green().to_string(),
-        1_000_000..=999_999_999 => "(pretty big)".bright_yellow().to_string(),
-        _ => "(extremely big)".bright_red().bold().to_string(),
+    // Other kinds of quantity have their own descriptions, but are
+    // still colored according to their size category.
+    let category = categories::find_category(&options.categories, number);
+    let label = match kinds::describe(
+        options.kind,
+        number,
+        options.currency.map(|currency| currency.currency),
+        options.lang,
+    ) {
+        Some(description) => Some(description),
+        None => category.map(|category| i18n::translate(options.lang, &category.label).to_string()),
+    };
+
+    let magnitude = if options.show_magnitude && number.is_finite() {
+        Some(format!("~10^{}", order_of_magnitude(number)))
+    } else {
+        None
+    };
+
+    let parts: Vec<String> = label.into_iter().chain(magnitude).collect();
+    if parts.is_empty() {
+        return None;
     }
+
+    let style = category
+        .map(|category| category.style.clone())
+        .unwrap_or_default();
+    Some((options.digits.render(&parts.join(", ")), style))
 }
 
-fn format_number_with_separators(number: f64) -> String {
-    let integer_part = number.trunc() as i64;
+/// The power of ten of `number`, e.g. 3 for 5,000 and -4 for 0.0001.
+fn order_of_magnitude(number: f64) -> i32 {
+    number.abs().log10().floor() as i32
+}
+
+/// Round `number` to 2 decimal places. Numbers from 2^53 up have no
+/// fractional part, and multiplying them by 100 would only add float
+/// error.
+fn round_to_hundredths(number: f64) -> f64 {
+    if number.abs() >= 2f64.powi(53) {
+        number
+    } else {
+        (number * 100.0).round() / 100.0
+    }
+}
+
+fn format_number_with_separators(number: f64, grouping: Grouping) -> String {
+    if !number.is_finite() {
+        return number.to_string();
+    }
+
+    // Group the digits as written out, as casting to an integer would
+    // saturate for numbers past i128.
+    let digits = format!("{:.0}", number.abs().trunc());
     let decimal_part = ((number.abs() - number.abs().trunc()) * 100.0).round() / 100.0;
 
-    let formatted_int = integer_part.to_formatted_string(&Locale::en);
+    let mut formatted_int = grouping::group_digits(&digits, grouping);
+    if number.trunc() < 0.0 {
+        formatted_int.insert(0, '-');
+    }
 
     if decimal_part > 0.0 {
         format!(
@@ -82,84 +727,702 @@ fn format_as_binary_units(number: f64) -> String {
     format_size(number as u64, BINARY)
 }
 
-fn format_single_number(number: f64) -> String {
-    let rounded = (number * 100.0).round() / 100.0;
-    format_number_with_separators(rounded)
+/// Format `number` in a short form using K, M, B and T suffixes, e.g.
+/// 1.2M, or the units of the grouping style, e.g. 1.2 crore or 1.2億.
+fn format_compact(number: f64, grouping: Grouping) -> String {
+    let units: Vec<(f64, &str)> = match grouping {
+        Grouping::Western => vec![(1.0, ""), (1e3, "K"), (1e6, "M"), (1e9, "B"), (1e12, "T")],
+        Grouping::Indian => vec![(1.0, ""), (1e3, "K"), (1e5, " lakh"), (1e7, " crore")],
+        Grouping::Myriad | Grouping::MyriadHangul => std::iter::once((1.0, ""))
+            .chain(
+                grouping::myriad_units(grouping)
+                    .iter()
+                    .zip(1..)
+                    .map(|(unit, power)| (10000f64.powi(power), *unit)),
+            )
+            .collect(),
+    };
+
+    let mut unit = 0;
+    while unit + 1 < units.len() && number.abs() >= units[unit + 1].0 {
+        unit += 1;
+    }
+
+    let round_scaled = |unit: usize| {
+        let scaled = number / units[unit].0;
+        // Small numbers keep up to two decimal places, like
+        // format_single_number, larger ones one.
+        let precision = if unit == 0 { 100.0 } else { 10.0 };
+        (scaled * precision).round() / precision
+    };
+
+    // 999,999 should be 1M rather than 1000K.
+    let mut scaled = round_scaled(unit);
+    if unit + 1 < units.len() && scaled.abs() >= units[unit + 1].0 / units[unit].0 {
+        unit += 1;
+        scaled = round_scaled(unit);
+    }
+
+    if scaled == 0.0 {
+        // Avoid showing -0 for small negative numbers.
+        scaled = 0.0;
+    }
+
+    let formatted = if scaled.abs() >= 1000.0 {
+        format_number_with_separators(scaled.round(), grouping)
+    } else {
+        scaled.to_string()
+    };
+    format!("{}{}", formatted, units[unit].1)
 }
 
-fn process_text_with_numbers(text: &str) -> String {
-    // Regex to match numbers including decimals and scientific notation
-    let re = Regex::new(r"-?\d+\.?\d*(?:[eE][+-]?\d+)?").unwrap();
+/// Format `number` in scientific notation with three significant
+/// figures, e.g. 1.23e6.
+fn format_scientific(number: f64) -> String {
+    let formatted = format!("{:.2e}", number);
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+            format!("{}e{}", mantissa, exponent)
+        }
+        _ => formatted,
+    }
+}
 
-    re.replace_all(text, |caps: &regex::Captures| {
-        let num_str = &caps[0];
-        if let Ok(number) = num_str.parse::<f64>() {
-            format_single_number(number)
-        } else {
-            num_str.to_string()
+fn format_single_number(number: f64, grouping: Grouping) -> String {
+    let rounded = round_to_hundredths(number);
+    format_number_with_separators(rounded, grouping)
+}
+
+/// Format a number found within text.
+fn format_embedded_number(number: f64, options: &Options) -> String {
+    if options.words {
+        words::number_to_words(number, options.scale, options.lang)
+    } else if let Some(notation) = &options.notation {
+        options.digits.render(&notation.format(number))
+    } else if let Some(ratio) = &options.ratio
+        && number != 0.0
+        && number.abs() < 1.0
+    {
+        // Only fractions are ratios; "3 of 1000 requests" stays as is.
+        options.digits.render(&ratio.format(number))
+    } else {
+        options
+            .digits
+            .render(&format_single_number(number, options.grouping))
+    }
+}
+
+/// Matches numbers including decimals and scientific notation. Only
+/// valid UTF-8 is matched, so the same pattern can be used on raw bytes
+/// without touching non-UTF-8 content.
+const NUMBER_PATTERN: &str = r"-?[0-9]+[.٫]?[0-9]*(?:[eE][+-]?[0-9]+)?";
+
+/// Matches numbers within text: digits with a scale such as "2.5
+/// million", numbers written in words, and plain numbers. Digits may be
+/// from any script in `digits::DIGIT_CLASS`.
+static TEXT_NUMBER_PATTERN: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "(?:{})|(?:{})|(?:{})",
+        parse::SHORTHAND_PATTERN,
+        parse::WORDS_PATTERN,
+        NUMBER_PATTERN
+    )
+    .replace("[0-9]", digits::DIGIT_CLASS)
+});
+
+static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&TEXT_NUMBER_PATTERN).unwrap());
+
+static NUMBER_BYTES_RE: LazyLock<regex::bytes::Regex> =
+    LazyLock::new(|| regex::bytes::Regex::new(&TEXT_NUMBER_PATTERN).unwrap());
+
+/// Like `TEXT_NUMBER_PATTERN`, but also matching amounts with a currency
+/// symbol, e.g. "$1234.5", "$1,234.50" or "-€5", which have a `symbol`
+/// group.
+static CURRENCY_PATTERN: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        r"(?:-?(?P<symbol>{})\s?(?:{}|{}))|{}",
+        currency::SYMBOL_PATTERN,
+        GROUPED_AMOUNT_PATTERN.replace("[0-9]", digits::DIGIT_CLASS),
+        NUMBER_PATTERN.replace("[0-9]", digits::DIGIT_CLASS),
+        *TEXT_NUMBER_PATTERN
+    )
+});
+
+/// Matches an amount with thousands separators, e.g. "1,234.50".
+const GROUPED_AMOUNT_PATTERN: &str = r"[0-9]{1,3}(?:,[0-9]{3})+(?:\.[0-9]+)?";
+
+static CURRENCY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&CURRENCY_PATTERN).unwrap());
+
+static CURRENCY_BYTES_RE: LazyLock<regex::bytes::Regex> =
+    LazyLock::new(|| regex::bytes::Regex::new(&CURRENCY_PATTERN).unwrap());
+
+/// Format an amount found in text, e.g. "$1234.5", as its currency.
+fn format_text_amount(text: &str, currency: &CurrencyFormat, options: &Options) -> String {
+    match currency.parse_amount(text) {
+        Some((amount, input_currency)) => {
+            let currency = CurrencyFormat {
+                currency: input_currency,
+                ..*currency
+            };
+            options
+                .digits
+                .render(&currency.format(amount, options.lang, |whole| {
+                    format_number_with_separators(whole, options.grouping)
+                }))
+        }
+        None => text.to_string(),
+    }
+}
+
+/// Like `TEXT_NUMBER_PATTERN`, but a number may be followed by a unit
+/// of time, e.g. "1500 ms", in a `time_unit` group.
+static DURATION_PATTERN: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        r"(?P<number>{})(?:\s*(?P<time_unit>{})\b)?",
+        *TEXT_NUMBER_PATTERN,
+        duration::TEXT_UNIT_PATTERN
+    )
+});
+
+static DURATION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&DURATION_PATTERN).unwrap());
+
+static DURATION_BYTES_RE: LazyLock<regex::bytes::Regex> =
+    LazyLock::new(|| regex::bytes::Regex::new(&DURATION_PATTERN).unwrap());
+
+/// Format a number followed by a unit of time in text, e.g. "1500 ms",
+/// as a duration. The unit in the text replaces --duration's.
+fn format_text_duration(
+    number: &str,
+    unit: &str,
+    duration: &DurationFormat,
+    options: &Options,
+) -> Option<String> {
+    let number = parse::parse_number_in_text(number)?;
+    let duration = DurationFormat {
+        unit: duration::text_unit(unit)?,
+        ..*duration
+    };
+    Some(options.digits.render(&duration.format(number)))
+}
+
+fn process_text_with_numbers(text: &str, options: &Options) -> String {
+    if let Some(duration) = &options.duration {
+        // Only numbers with a unit are durations; "3 retries" isn't.
+        return DURATION_RE
+            .replace_all(text, |caps: &regex::Captures| {
+                match caps.name("time_unit") {
+                    Some(unit) => {
+                        format_text_duration(&caps["number"], unit.as_str(), duration, options)
+                            .unwrap_or_else(|| caps[0].to_string())
+                    }
+                    None => format_text_number(&caps[0], options),
+                }
+            })
+            .to_string();
+    }
+
+    if let Some(currency) = &options.currency {
+        return CURRENCY_RE
+            .replace_all(text, |caps: &regex::Captures| match caps.name("symbol") {
+                Some(_) => format_text_amount(&caps[0], currency, options),
+                None => format_text_number(&caps[0], options),
+            })
+            .to_string();
+    }
+
+    let Some(si) = &options.si else {
+        return NUMBER_RE
+            .replace_all(text, |caps: &regex::Captures| {
+                format_text_number(&caps[0], options)
+            })
+            .to_string();
+    };
+
+    si.text_re
+        .replace_all(text, |caps: &regex::Captures| {
+            let number = parse::parse_number_in_text(&caps["number"]);
+            match (number, caps.name("unit")) {
+                (Some(number), Some(_)) => {
+                    let prefix = caps.name("prefix").map_or("", |m| m.as_str());
+                    options
+                        .digits
+                        .render(&si.format(number * si::prefix_multiplier(prefix)))
+                }
+                _ => format_text_number(&caps[0], options),
+            }
+        })
+        .to_string()
+}
+
+/// Format a number found in text, or return it unchanged if it isn't
+/// really a number.
+fn format_text_number(num_str: &str, options: &Options) -> String {
+    if let Some(timestamps) = &options.timestamps
+        && let Some(timestamp) = timestamp::parse_timestamp(num_str)
+    {
+        return timestamps.format(timestamp);
+    }
+
+    match parse::parse_number_in_text(num_str) {
+        Some(number) => format_embedded_number(number, options),
+        None => num_str.to_string(),
+    }
+}
+
+/// Like `process_text_with_numbers`, but for input that isn't valid
+/// UTF-8. Everything other than the numbers is passed through unchanged.
+fn process_bytes_with_numbers(bytes: &[u8], options: &Options) -> Vec<u8> {
+    let re = match (&options.si, &options.currency, &options.duration) {
+        (Some(si), _, _) => &si.bytes_re,
+        (None, Some(_), _) => &*CURRENCY_BYTES_RE,
+        (None, None, Some(_)) => &*DURATION_BYTES_RE,
+        (None, None, None) => &*NUMBER_BYTES_RE,
+    };
+    re.replace_all(bytes, |caps: &regex::bytes::Captures| {
+        let Ok(num_str) = std::str::from_utf8(&caps[0]) else {
+            return caps[0].to_vec();
+        };
+        if let Some(currency) = &options.currency
+            && caps.name("symbol").is_some()
+        {
+            return format_text_amount(num_str, currency, options).into_bytes();
+        }
+        if let Some(duration) = &options.duration
+            && let Some(unit) = caps.name("time_unit")
+        {
+            // The number and unit are both valid UTF-8, as the whole
+            // match is.
+            let number = &num_str[..caps["number"].len()];
+            let unit = &num_str[unit.start() - caps.get(0).map_or(0, |m| m.start())..];
+            return format_text_duration(number, unit, duration, options)
+                .unwrap_or_else(|| num_str.to_string())
+                .into_bytes();
+        }
+        match (&options.si, caps.name("unit")) {
+            (Some(si), Some(_)) => {
+                // The number alone, without the unit.
+                let number = std::str::from_utf8(&caps["number"])
+                    .ok()
+                    .and_then(parse::parse_number_in_text);
+                let prefix = caps
+                    .name("prefix")
+                    .and_then(|m| std::str::from_utf8(m.as_bytes()).ok())
+                    .unwrap_or("");
+                match number {
+                    Some(number) => options
+                        .digits
+                        .render(&si.format(number * si::prefix_multiplier(prefix)))
+                        .into_bytes(),
+                    None => caps[0].to_vec(),
+                }
+            }
+            _ => format_text_number(num_str, options).into_bytes(),
         }
     })
-    .to_string()
+    .into_owned()
 }
 
-fn process_number(input: &str, show_bytes: bool) {
+/// Process a single line of stdin, excluding its line terminator.
+fn process_line(line: &[u8], options: &Options) -> Vec<u8> {
+    if options.json {
+        return json::line_to_json(line, options).into_bytes();
+    }
+    if let Some(version) = options.porcelain {
+        return porcelain::line_to_porcelain(line, version, options).into_bytes();
+    }
+
+    match std::str::from_utf8(line) {
+        Ok(text) => process_number(text, options).into_bytes(),
+        // Not valid UTF-8 (e.g. Latin-1 or binary data), so it can't be a
+        // pure number. Rewrite the digit runs and leave everything else
+        // alone.
+        Err(_) => process_bytes_with_numbers(line, options),
+    }
+}
+
+/// Split a record read from stdin into its content and its terminator
+/// (`\n`, `\r\n`, `\0` in --zero-terminated mode, or nothing for a
+/// final record without a terminator).
+fn split_record_terminator(record: &[u8], delimiter: u8) -> (&[u8], &[u8]) {
+    let content_len = if delimiter == b'\n' && record.ends_with(b"\r\n") {
+        record.len() - 2
+    } else if record.last() == Some(&delimiter) {
+        record.len() - 1
+    } else {
+        record.len()
+    };
+    record.split_at(content_len)
+}
+
+fn process_number(input: &str, options: &Options) -> String {
     let trimmed = input.trim();
 
-    // Try to parse as a single number first
-    match trimmed.parse::<f64>() {
-        Ok(number) => {
-            // Round to 2 decimal places
-            let rounded = (number * 100.0).round() / 100.0;
+    if let Some(timestamps) = &options.timestamps
+        && let Some(timestamp) = timestamp::parse_timestamp(trimmed)
+    {
+        let description = timestamps.describe(timestamp, options.lang);
+        return format!(
+            "{} ({})",
+            timestamps.format(timestamp),
+            options.digits.render(&description)
+        );
+    }
 
-            if show_bytes {
-                // When -b flag is passed, only show binary format without color
-                let binary_format = format_as_binary_units(rounded);
-                println!("{}", binary_format);
-            } else {
-                // Default behavior: show formatted number with description
-                // Check if rounding occurred
-                let was_rounded = (number - rounded).abs() > f64::EPSILON;
-
-                let formatted = format_number_with_separators(rounded);
-                let rounded_text = if was_rounded {
-                    " (rounded)".dimmed().to_string()
-                } else {
-                    String::new()
-                };
-
-                println!(
-                    "{}{} {}",
-                    formatted,
-                    rounded_text,
-                    get_size_description(rounded)
-                );
+    // "$1234.5" is an amount in dollars, whatever --currency says.
+    if let Some(currency) = &options.currency
+        && let Some((amount, input_currency)) = currency.parse_amount(trimmed)
+    {
+        let options = Options {
+            currency: Some(CurrencyFormat {
+                currency: input_currency,
+                ..*currency
+            }),
+            ..options.clone()
+        };
+        return format_number_for_display(amount, &options);
+    }
+
+    // Try to parse as a single number first. With --duration, "5m" is
+    // five minutes rather than five million, and "1h30m" is understood.
+    let number = match &options.duration {
+        Some(duration) => duration::parse_duration(trimmed)
+            .map(|seconds| seconds / duration.unit.seconds())
+            .or_else(|| parse::parse_number(trimmed)),
+        None => parse::parse_number(trimmed),
+    };
+    match number {
+        Some(number) if options.words => {
+            words::number_to_words(number, options.scale, options.lang)
+        }
+        Some(number) => match &options.format {
+            Some(template) => format_with_template(number, template, options),
+            None => match ratio_input_options(trimmed, options) {
+                Some(ratio_options) => format_number_for_display(number, &ratio_options),
+                _ => format_number_for_display(number, options),
+            },
+        },
+        None => {
+            // Not a pure number, treat as text with embedded numbers.
+            // Whitespace is preserved so indentation-sensitive input
+            // (YAML, Makefiles) survives the round trip.
+            process_text_with_numbers(input, options)
+        }
+    }
+}
+
+/// If `input` is written as a ratio, e.g. "12.5%", options for showing
+/// it that way, unless we've been asked to show numbers some other way.
+fn ratio_input_options(input: &str, options: &Options) -> Option<Options> {
+    let (_, unit) = ratio::parse_ratio(input)?;
+    let other_format = options.ratio.is_some()
+        || options.show_bytes
+        || options.si.is_some()
+        || options.notation.is_some()
+        || options.duration.is_some();
+    if other_format {
+        return None;
+    }
+
+    let kind = match options.kind {
+        Kind::Number => Kind::Ratio,
+        kind => kind,
+    };
+    // Show at least as many decimal places as the input has, so 0.001%
+    // isn't shown as 0%.
+    let decimals = digits::to_ascii(input)
+        .split_once('.')
+        .map_or(0, |(_, fraction)| {
+            fraction.chars().take_while(char::is_ascii_digit).count()
+        });
+    Some(Options {
+        ratio: Some(RatioFormat {
+            unit,
+            precision: decimals.max(2),
+        }),
+        kind,
+        references: options
+            .references
+            .as_ref()
+            .map(|_| compare::default_references(kind)),
+        ..options.clone()
+    })
+}
+
+/// Format `number` in nn's default style, e.g. "42.12 (rounded) (small)".
+fn format_number_for_display(number: f64, options: &Options) -> String {
+    // Round to 2 decimal places
+    let rounded = round_to_hundredths(number);
+
+    let (formatted, rounded_text) = if let Some(duration) = &options.duration {
+        (duration.format(number), String::new())
+    } else if let Some(ratio) = &options.ratio {
+        (ratio.format(number), String::new())
+    } else if let Some(currency) = &options.currency {
+        let formatted = currency.format(number, options.lang, |whole| {
+            format_number_with_separators(whole, options.grouping)
+        });
+        let rounded_text = if currency.is_rounded(number) {
+            rounded_note(options.lang)
+        } else {
+            String::new()
+        };
+        (formatted, rounded_text)
+    } else if let Some(si) = &options.si {
+        // Like binary units, SI prefixes are approximate anyway.
+        (si.format(number), String::new())
+    } else if let Some(notation) = &options.notation {
+        // Significant figures already say how precise the number is.
+        (notation.format(number), String::new())
+    } else if options.show_bytes {
+        // When -b flag is passed, show binary format. Binary
+        // units are approximate anyway, so there's no
+        // "(rounded)" note.
+        (format_as_binary_units(rounded), String::new())
+    } else {
+        // Check if rounding occurred
+        let was_rounded = (number - rounded).abs() > f64::EPSILON;
+
+        let rounded_text = if was_rounded {
+            rounded_note(options.lang)
+        } else {
+            String::new()
+        };
+        (
+            format_number_with_separators(rounded, options.grouping),
+            rounded_text,
+        )
+    };
+
+    // Describe the original value, so small fractions aren't
+    // described as zero just because they round to it. Durations are
+    // described in seconds, whatever unit they're given in.
+    let number = match &options.duration {
+        Some(duration) => number * duration.unit.seconds(),
+        None => number,
+    };
+    let description = get_size_description(number, options);
+    let mut output = format!("{}{}", options.digits.render(&formatted), rounded_text);
+    if !description.is_empty() {
+        output.push(' ');
+        output.push_str(&description);
+    }
+
+    let comparison = options
+        .references
+        .as_ref()
+        .and_then(|references| compare::compare(number, references, options.lang));
+    if let Some(comparison) = comparison {
+        output.push(' ');
+        output.push_str(&comparison);
+    }
+
+    output
+}
+
+/// The note shown after a number that's been rounded.
+fn rounded_note(lang: Lang) -> String {
+    format!(" ({})", i18n::translate(lang, "rounded"))
+        .dimmed()
+        .to_string()
+}
+
+/// Format `number` according to the --format template.
+fn format_with_template(number: f64, template: &Template, options: &Options) -> String {
+    let rounded = round_to_hundredths(number);
+    let was_rounded = (number - rounded).abs() > f64::EPSILON;
+
+    let digits = options.digits;
+    template.render(|field| match field {
+        Field::Value => digits.render(&number.to_string()),
+        Field::Grouped => digits.render(&format_number_with_separators(rounded, options.grouping)),
+        Field::Compact => digits.render(&format_compact(number, options.grouping)),
+        Field::Bytes => digits.render(&format_as_binary_units(rounded)),
+        Field::Desc => match describe_size(number, options) {
+            Some((description, style)) => style.apply(&description).to_string(),
+            None => String::new(),
+        },
+        Field::Rounded if was_rounded => format!("({})", i18n::translate(options.lang, "rounded"))
+            .dimmed()
+            .to_string(),
+        Field::Rounded => String::new(),
+        Field::Sci => digits.render(&format_scientific(number)),
+        Field::Words => words::number_to_words(number, options.scale, options.lang),
+    })
+}
+
+/// Process input line by line. Lines are read as raw bytes rather than
+/// strings, so invalid UTF-8 doesn't stop processing, and each line's
+/// terminator is kept so it can be written back unchanged.
+///
+/// Output is buffered, and flushed whenever we've run out of buffered
+/// input (or after every line, with --line-buffered). This keeps
+/// large files fast whilst still showing results promptly when input
+/// arrives slowly, e.g. from an interactive terminal.
+fn process_lines<R: Read>(mut input: BufReader<R>, options: &Options) -> io::Result<()> {
+    let delimiter = options.delimiter;
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if input.read_until(delimiter, &mut line)? == 0 {
+            break;
+        }
+
+        let (content, terminator) = split_record_terminator(&line, delimiter);
+        if content.trim_ascii().is_empty() && options.json {
+            // Blank lines have nothing to describe in JSON.
+            continue;
+        } else if content.trim_ascii().is_empty() && options.porcelain.is_none() {
+            stdout.write_all(content)?;
+        } else if options.json || options.porcelain.is_some() {
+            stdout.write_all(&process_line(content, options))?;
+        } else {
+            // Put the whitespace around a lone number back, so indented
+            // lines in YAML or a Makefile keep their indentation.
+            let trimmed = content.trim_ascii();
+            let leading = content.len() - content.trim_ascii_start().len();
+            stdout.write_all(&content[..leading])?;
+            stdout.write_all(&process_line(trimmed, options))?;
+            stdout.write_all(&content[leading + trimmed.len()..])?;
+        }
+        stdout.write_all(terminator)?;
+
+        if options.line_buffered || input.buffer().is_empty() {
+            stdout.flush()?;
+        }
+    }
+
+    stdout.flush()
+}
+
+/// How often to check a followed file for new data.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A reader that never reaches EOF: when it runs out of data it waits
+/// for more to be appended, like `tail -F`. If the file doesn't exist
+/// yet, it waits for it to be created. If the file is rotated
+/// (replaced by a new file at the same path) or truncated, it starts
+/// again from the beginning of the new contents.
+struct FollowReader {
+    path: PathBuf,
+    file: File,
+    position: u64,
+}
+
+impl FollowReader {
+    /// Open `path`, waiting for it to be created if it doesn't exist yet.
+    fn open(path: &Path) -> io::Result<Self> {
+        let mut waiting = false;
+        let file = loop {
+            match File::open(path) {
+                Ok(file) => break file,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    if !waiting {
+                        eprintln!("nn: {}: {}, waiting for it to appear", path.display(), e);
+                        waiting = true;
+                    }
+                    thread::sleep(FOLLOW_POLL_INTERVAL);
+                }
+                Err(e) => return Err(e),
             }
+        };
+
+        Ok(FollowReader {
+            path: path.to_path_buf(),
+            file,
+            position: 0,
+        })
+    }
+
+    /// If the file at our path is no longer the file we're reading, or
+    /// it has been truncated, switch to reading it from the start. Like
+    /// `tail -F`, a rotated file is read to the end first, so lines
+    /// written just before it was moved aside aren't lost.
+    fn check_rotation(&mut self) -> io::Result<()> {
+        // The file may briefly not exist whilst it's being rotated, so
+        // keep reading the old one until a new one appears.
+        let Ok(path_metadata) = fs::metadata(&self.path) else {
+            return Ok(());
+        };
+
+        let file_metadata = self.file.metadata()?;
+        if !same_file(&path_metadata, &file_metadata) {
+            if file_metadata.len() > self.position {
+                return Ok(());
+            }
+            self.file = File::open(&self.path)?;
+            self.position = 0;
+        } else if path_metadata.len() < self.position {
+            self.file.seek(SeekFrom::Start(0))?;
+            self.position = 0;
         }
-        Err(_) => {
-            // Not a pure number, treat as text with embedded numbers
-            let processed = process_text_with_numbers(trimmed);
-            println!("{}", processed);
+
+        Ok(())
+    }
+}
+
+impl Read for FollowReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.file.read(buf)?;
+            if n > 0 {
+                self.position += n as u64;
+                return Ok(n);
+            }
+
+            thread::sleep(FOLLOW_POLL_INTERVAL);
+            self.check_rotation()?;
         }
     }
 }
 
-fn main() {
-    let cli = Cli::parse();
+#[cfg(unix)]
+fn same_file(a: &Metadata, b: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+/// Without inode numbers we can't detect rotation, only truncation.
+#[cfg(not(unix))]
+fn same_file(_a: &Metadata, _b: &Metadata) -> bool {
+    true
+}
 
-    if let Some(number_arg) = cli.number {
+fn run(cli: &Cli) -> io::Result<()> {
+    let options = Options::from_cli(cli)?;
+
+    if let Some(number_arg) = &cli.number {
         // Process number from command-line argument
-        process_number(&number_arg, cli.bytes);
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(&process_line(number_arg.as_bytes(), &options))?;
+        stdout.write_all(&[options.delimiter])?;
+        stdout.flush()
+    } else if let Some(path) = &cli.follow {
+        let reader = FollowReader::open(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        process_lines(BufReader::new(reader), &options)
     } else {
-        // Process number from stdin
-        let stdin = io::stdin();
-        let lines = stdin.lock().lines();
+        // Our own reader (rather than the StdinLock) so we can see how
+        // much input is buffered.
+        let stdin = BufReader::with_capacity(64 * 1024, io::stdin().lock());
+        process_lines(stdin, &options)
+    }
+}
 
-        for line in lines.map_while(Result::ok) {
-            if line.trim().is_empty() {
-                println!();
-            } else {
-                process_number(&line, cli.bytes);
-            }
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    colored::control::set_override(should_color_stdout(cli.color));
+
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader went away (e.g. `nn < big.log | head`), so there's
+        // nobody left to report anything to.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("nn: {}", e);
+            ExitCode::FAILURE
         }
     }
 }
@@ -0,0 +1,86 @@
+//! Scientific and engineering notation, such as 1.23×10^5 or 123×10^3.
+
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// One digit before the decimal point: 1.23×10^5
+    Scientific,
+    /// Exponents are a multiple of three: 123×10^3
+    Engineering,
+}
+
+/// How to write the exponent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExponentStyle {
+    /// 1.23e5
+    E,
+    /// 1.23×10^5
+    Times,
+    /// 1.23×10⁵
+    Superscript,
+}
+
+/// Settings for --sci and --eng.
+#[derive(Clone, Copy, Debug)]
+pub struct NotationFormat {
+    pub notation: Notation,
+    pub sig_figs: usize,
+    pub style: ExponentStyle,
+}
+
+impl NotationFormat {
+    /// Format `number` with `sig_figs` significant figures, keeping
+    /// trailing zeros as they're significant: 1e6 is 1.00×10^6.
+    pub fn format(&self, number: f64) -> String {
+        if !number.is_finite() {
+            return number.to_string();
+        }
+        // Avoid showing -0.00×10^0.
+        let number = if number == 0.0 { 0.0 } else { number };
+
+        // Let the standard library do the rounding, which also handles
+        // 9.999 becoming 1.00e1.
+        let sci = format!("{:.*e}", self.sig_figs - 1, number);
+        let (mantissa, exponent) = sci.split_once('e').unwrap_or((&sci, "0"));
+        let exponent: i32 = exponent.parse().unwrap_or(0);
+
+        let (mantissa, exponent) = match self.notation {
+            Notation::Scientific => (mantissa.to_string(), exponent),
+            Notation::Engineering => {
+                let shift = exponent.rem_euclid(3);
+                let mantissa = mantissa.parse::<f64>().unwrap_or(0.0) * 10f64.powi(shift);
+                let decimals = (self.sig_figs - 1).saturating_sub(shift as usize);
+                (format!("{:.*}", decimals, mantissa), exponent - shift)
+            }
+        };
+
+        match self.style {
+            ExponentStyle::E => format!("{}e{}", mantissa, exponent),
+            ExponentStyle::Times => format!("{}×10^{}", mantissa, exponent),
+            ExponentStyle::Superscript => format!("{}×10{}", mantissa, superscript(exponent)),
+        }
+    }
+}
+
+/// Write `exponent` in Unicode superscript characters, e.g. ⁻¹².
+fn superscript(exponent: i32) -> String {
+    exponent
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            c => c,
+        })
+        .collect()
+}
@@ -0,0 +1,274 @@
+//! Parsing numbers written in words or shorthand, such as "three
+//! thousand", "two point five million", "a dozen" or "1.2bn".
+
+use crate::{digits, ratio};
+
+const UNITS: [(&str, f64); 28] = [
+    ("zero", 0.0),
+    ("one", 1.0),
+    ("two", 2.0),
+    ("three", 3.0),
+    ("four", 4.0),
+    ("five", 5.0),
+    ("six", 6.0),
+    ("seven", 7.0),
+    ("eight", 8.0),
+    ("nine", 9.0),
+    ("ten", 10.0),
+    ("eleven", 11.0),
+    ("twelve", 12.0),
+    ("thirteen", 13.0),
+    ("fourteen", 14.0),
+    ("fifteen", 15.0),
+    ("sixteen", 16.0),
+    ("seventeen", 17.0),
+    ("eighteen", 18.0),
+    ("nineteen", 19.0),
+    ("twenty", 20.0),
+    ("thirty", 30.0),
+    ("forty", 40.0),
+    ("fifty", 50.0),
+    ("sixty", 60.0),
+    ("seventy", 70.0),
+    ("eighty", 80.0),
+    ("ninety", 90.0),
+];
+
+/// Words that multiply everything before them, e.g. "two point five
+/// million".
+const SCALES: [(&str, f64); 7] = [
+    ("thousand", 1e3),
+    ("lakh", 1e5),
+    ("crore", 1e7),
+    ("million", 1e6),
+    ("billion", 1e9),
+    ("trillion", 1e12),
+    ("quadrillion", 1e15),
+];
+
+/// Suffixes that can follow digits, e.g. 1.2bn or 3k.
+const SUFFIXES: [(&str, f64); 8] = [
+    ("k", 1e3),
+    ("m", 1e6),
+    ("mn", 1e6),
+    ("mm", 1e6),
+    ("b", 1e9),
+    ("bn", 1e9),
+    ("t", 1e12),
+    ("tn", 1e12),
+];
+
+/// A number up to ninety-nine in words, e.g. "twenty-one" or "three
+/// point one four", for `WORDS_PATTERN`.
+macro_rules! small_number_pattern {
+    () => {
+        concat!(
+            r"(?:(?:twenty|thirty|forty|fifty|sixty|seventy|eighty|ninety)\b",
+            r"(?:[\s-]+(?:one|two|three|four|five|six|seven|eight|nine)\b)?",
+            r"|(?:zero|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve",
+            r"|thirteen|fourteen|fifteen|sixteen|seventeen|eighteen|nineteen)\b)",
+            r"(?:\s+point(?:\s+(?:zero|one|two|three|four|five|six|seven|eight|nine)\b)+)?",
+        )
+    };
+}
+
+/// A multiplier word, followed by an optional number: "hundred and five".
+macro_rules! multiplier_pattern {
+    () => {
+        concat!(
+            r"(?:hundred|thousand|lakh|crore|million|billion|trillion|quadrillion|dozen)\b",
+            r"(?:[\s-]+(?:and\s+)?",
+            small_number_pattern!(),
+            r")?",
+        )
+    };
+}
+
+/// Matches a number written in words, e.g. "twenty-one" or "a hundred and
+/// five". A leading "a" or "an" is only matched before a multiplier, so
+/// "a cat" isn't a number. Numbers must be joined by a multiplier, so "one
+/// two three" is three separate words.
+pub const WORDS_PATTERN: &str = concat!(
+    r"(?i)\b(?:(?:minus|negative)\s+)?",
+    r"(?:an?\s+",
+    multiplier_pattern!(),
+    r"|",
+    small_number_pattern!(),
+    r")(?:[\s-]+(?:and\s+)?",
+    multiplier_pattern!(),
+    r")*",
+);
+
+/// Matches digits followed by a scale, e.g. "2.5 million" or "1.2bn".
+/// Single-letter suffixes like "5m" are too ambiguous in running text
+/// (metres? minutes?), so they're only recognised by `parse_number`.
+pub const SHORTHAND_PATTERN: &str = concat!(
+    r"(?i)-?\b[0-9]+(?:[.٫][0-9]+)?",
+    r"(?:\s*bn|\s+(?:thousand|lakh|crore|million|billion|trillion|quadrillion))\b",
+);
+
+/// Parse `text` as a number, which may be written with digits ("2500"
+/// or "٢٥٠٠"), in words ("two point five thousand") or in shorthand
+/// ("2.5k"). Ratios such as "12.5%" are read as a fraction.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = digits::to_ascii(text.trim());
+    let text = text.as_ref();
+    if let Ok(number) = text.parse::<f64>() {
+        return Some(number);
+    }
+
+    parse_shorthand(text)
+        .or_else(|| ratio::parse_ratio(text).map(|(fraction, _)| fraction))
+        .or_else(|| parse_words(text))
+}
+
+/// Like `parse_number`, but for a number found in running text. A lone
+/// word such as "one" is left alone, as it's rarely meant as a number
+/// ("no one came").
+pub fn parse_number_in_text(text: &str) -> Option<f64> {
+    if text.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    parse_number(text)
+}
+
+/// Parse digits followed by a suffix or scale word, e.g. 1.2bn, 3k or
+/// "2.5 million".
+fn parse_shorthand(text: &str) -> Option<f64> {
+    let split_at = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .filter(|i| *i > 0)?;
+    let (digits, suffix) = text.split_at(split_at);
+
+    let number = digits.parse::<f64>().ok()?;
+    let suffix = suffix.trim().to_lowercase();
+    let multiplier = SUFFIXES
+        .iter()
+        .chain(SCALES.iter())
+        .find(|(name, _)| *name == suffix)?
+        .1;
+
+    Some(number * multiplier)
+}
+
+/// Parse a number written in words, e.g. "minus two hundred and five" or
+/// "three point one four". Digits may be used for the leading part, as
+/// in "1.5 million".
+fn parse_words(text: &str) -> Option<f64> {
+    let lowercase = text.to_lowercase();
+    let mut tokens = lowercase
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|token| !token.is_empty() && *token != "and")
+        .peekable();
+
+    let negative = matches!(tokens.peek(), Some(&"minus") | Some(&"negative"));
+    if negative {
+        tokens.next();
+    }
+
+    // `total` holds everything up to the last scale word, `current` the
+    // part since then.
+    let mut total = 0.0;
+    let mut current = 0.0;
+    let mut seen_number = false;
+    // What the previous word was, which limits what can follow it: "one
+    // two" and "ten four" aren't numbers, but "twenty-one" is.
+    let mut previous = Previous::Start;
+
+    while let Some(token) = tokens.next() {
+        let is_multiplier =
+            token == "hundred" || token == "dozen" || SCALES.iter().any(|(name, _)| *name == token);
+        let unit = UNITS
+            .iter()
+            .find(|(name, _)| *name == token)
+            .map(|(_, value)| *value);
+        let allowed = match previous {
+            Previous::Start | Previous::Multiplier => true,
+            Previous::Tens => {
+                is_multiplier
+                    || token == "point"
+                    || unit.is_some_and(|value| (1.0..10.0).contains(&value))
+            }
+            Previous::Unit => is_multiplier || token == "point",
+            Previous::Article | Previous::Fraction => is_multiplier,
+        };
+        if !allowed {
+            return None;
+        }
+        if is_multiplier {
+            previous = Previous::Multiplier;
+        }
+
+        if let Some(value) = unit {
+            current += value;
+            previous = if value >= 20.0 {
+                Previous::Tens
+            } else {
+                Previous::Unit
+            };
+        } else if (token == "a" || token == "an") && !seen_number {
+            current = 1.0;
+            previous = Previous::Article;
+        } else if token == "hundred" {
+            current = one_if_zero(current) * 100.0;
+        } else if token == "dozen" {
+            current = one_if_zero(current) * 12.0;
+        } else if let Some((_, scale)) = SCALES.iter().find(|(name, _)| *name == token) {
+            total += one_if_zero(current) * scale;
+            current = 0.0;
+        } else if token == "point" && seen_number {
+            // Read digit words as the fractional part.
+            let mut fraction = String::from("0.");
+            while let Some(digit) = tokens
+                .peek()
+                .and_then(|next| UNITS[..10].iter().position(|(name, _)| name == next))
+            {
+                fraction.push_str(&digit.to_string());
+                tokens.next();
+            }
+            if fraction.len() == 2 {
+                return None;
+            }
+            current += fraction.parse::<f64>().ok()?;
+            previous = Previous::Fraction;
+        } else if !seen_number {
+            // Allow digits to start the phrase, e.g. "1.5 million".
+            current = token.parse::<f64>().ok()?;
+            previous = Previous::Unit;
+        } else {
+            return None;
+        }
+
+        seen_number = true;
+    }
+
+    if !seen_number || previous == Previous::Article {
+        return None;
+    }
+
+    let number = total + current;
+    Some(if negative { -number } else { number })
+}
+
+/// The kind of the previous word in `parse_words`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Previous {
+    /// Nothing yet.
+    Start,
+    /// "a" or "an", which must be followed by a multiplier.
+    Article,
+    /// A word below twenty, or digits, followed by a multiplier or "point".
+    Unit,
+    /// "twenty" to "ninety", optionally followed by "one" to "nine".
+    Tens,
+    /// "hundred", "dozen" or a scale such as "million".
+    Multiplier,
+    /// The digits after "point", followed only by a multiplier.
+    Fraction,
+}
+
+/// The number to multiply by a multiplier word: "hundred" on its own
+/// means one hundred.
+fn one_if_zero(current: f64) -> f64 {
+    if current == 0.0 { 1.0 } else { current }
+}
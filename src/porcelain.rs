@@ -0,0 +1,52 @@
+//! Output for --porcelain, a tab-separated format for scripts.
+//!
+//! Version 1 of the format is a contract: it will not change in future
+//! releases. Changes will be made in a new version instead.
+//!
+//! Each input record produces exactly one row of five tab-separated
+//! columns:
+//!
+//! 1. VALUE: the number in plain decimal, without separators or an
+//!    exponent, e.g. `1536.123`.
+//! 2. GROUPED: the number rounded to 2 decimal places with `,` as the
+//!    thousands separator, e.g. `1,536.12`. Digits are always grouped
+//!    in threes, whatever the locale.
+//! 3. CATEGORY: the machine-readable size category, e.g. `medium` or
+//!    `pretty_big`, or empty if no category applies.
+//! 4. ROUNDED: `1` if GROUPED was rounded, otherwise `0`.
+//! 5. BYTES: the number in binary units, e.g. `1.50 KiB`.
+//!
+//! Records that aren't a single number produce a row with all five
+//! columns empty, so output rows always correspond to input records.
+
+use crate::{Options, categories, grouping::Grouping, parse};
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PorcelainVersion {
+    V1,
+}
+
+/// Describe a record (excluding its terminator) as a porcelain row.
+pub fn line_to_porcelain(line: &[u8], version: PorcelainVersion, options: &Options) -> String {
+    let number = std::str::from_utf8(line).ok().and_then(parse::parse_number);
+
+    match (version, number) {
+        (PorcelainVersion::V1, Some(number)) => v1_row(number, options),
+        (PorcelainVersion::V1, None) => "\t".repeat(4),
+    }
+}
+
+fn v1_row(number: f64, options: &Options) -> String {
+    let rounded = crate::round_to_hundredths(number);
+    let was_rounded = (number - rounded).abs() > f64::EPSILON;
+
+    let columns = [
+        number.to_string(),
+        crate::format_number_with_separators(rounded, Grouping::Western),
+        categories::category_name(number, &options.categories).unwrap_or_default(),
+        if was_rounded { "1" } else { "0" }.to_string(),
+        crate::format_as_binary_units(rounded),
+    ];
+    columns.join("\t")
+}
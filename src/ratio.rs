@@ -0,0 +1,92 @@
+//! Ratios shown as percentages, per-mille or basis points, such as
+//! 5.23%, 52.3‰ or 523 bp.
+
+use crate::digits;
+
+/// How to show a ratio.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RatioUnit {
+    /// Parts per hundred: 5.23%
+    Percent,
+    /// Parts per thousand: 52.3‰
+    PerMille,
+    /// Parts per ten thousand: 523 bp
+    BasisPoints,
+}
+
+impl RatioUnit {
+    fn per_one(self) -> f64 {
+        match self {
+            RatioUnit::Percent => 100.0,
+            RatioUnit::PerMille => 1_000.0,
+            RatioUnit::BasisPoints => 10_000.0,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            RatioUnit::Percent => "%",
+            RatioUnit::PerMille => "‰",
+            RatioUnit::BasisPoints => " bp",
+        }
+    }
+}
+
+/// Settings for --percent, --per-mille and --basis-points.
+#[derive(Clone, Copy, Debug)]
+pub struct RatioFormat {
+    pub unit: RatioUnit,
+    /// The maximum number of decimal places.
+    pub precision: usize,
+}
+
+impl RatioFormat {
+    /// Format `fraction` in `self.unit`, e.g. 0.0523 as 5.23%. Trailing
+    /// zeros are left out, so 0.5 is 50% rather than 50.00%.
+    pub fn format(&self, fraction: f64) -> String {
+        let value = fraction * self.unit.per_one();
+        if !value.is_finite() {
+            return format!("{}{}", value, self.unit.symbol());
+        }
+
+        let mut formatted = format!("{:.*}", self.precision, value);
+        if formatted.contains('.') {
+            formatted = formatted
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string();
+        }
+        // Avoid showing -0% for tiny negative fractions.
+        if formatted == "-0" {
+            formatted = "0".to_string();
+        }
+        format!("{}{}", formatted, self.unit.symbol())
+    }
+}
+
+/// Parse a percentage, per-mille or basis points, e.g. "12.5%", "5‰"
+/// or "25bp", returning the fraction it represents (0.125, 0.005 or
+/// 0.0025) and the unit it was written in.
+pub fn parse_ratio(text: &str) -> Option<(f64, RatioUnit)> {
+    let text = digits::to_ascii(text.trim());
+    let text = text.as_ref();
+
+    let (number, unit) = if let Some(number) = text.strip_suffix('%') {
+        (number, RatioUnit::Percent)
+    } else if let Some(number) = text.strip_suffix('‰') {
+        (number, RatioUnit::PerMille)
+    } else {
+        let lowercase = text.to_ascii_lowercase();
+        let suffix_len = if lowercase.ends_with("bps") {
+            3
+        } else if lowercase.ends_with("bp") {
+            2
+        } else {
+            return None;
+        };
+        (&text[..text.len() - suffix_len], RatioUnit::BasisPoints)
+    };
+
+    let number: f64 = number.trim_end().parse().ok()?;
+    Some((number / unit.per_one(), unit))
+}
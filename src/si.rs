@@ -0,0 +1,108 @@
+//! SI prefixes for physical quantities, such as 12.5 µs or 2.4 GHz.
+
+use regex::Regex;
+
+/// SI prefixes and their powers of ten, smallest first.
+const PREFIXES: [(&str, i32); 17] = [
+    ("y", -24),
+    ("z", -21),
+    ("a", -18),
+    ("f", -15),
+    ("p", -12),
+    ("n", -9),
+    ("µ", -6),
+    ("m", -3),
+    ("", 0),
+    ("k", 3),
+    ("M", 6),
+    ("G", 9),
+    ("T", 12),
+    ("P", 15),
+    ("E", 18),
+    ("Z", 21),
+    ("Y", 24),
+];
+
+/// The prefixes recognised in text. Rarer ones are left out, so "5 as
+/// expected" isn't read as 5 attoseconds.
+const TEXT_PREFIXES: &str = "p|n|µ|μ|u|m|k|M|G|T";
+
+/// A unit given with --si-unit, and regexes for finding numbers with
+/// that unit in text.
+#[derive(Clone, Debug)]
+pub struct SiUnit {
+    pub unit: String,
+    pub text_re: Regex,
+    pub bytes_re: regex::bytes::Regex,
+}
+
+impl SiUnit {
+    /// `number_pattern` matches numbers in text. A number may be
+    /// followed by the unit, with or without a prefix, e.g. "125 ms".
+    pub fn new(unit: &str, number_pattern: &str) -> Result<SiUnit, regex::Error> {
+        // Units ending in a letter shouldn't match the start of a word,
+        // e.g. "s" in "5 seconds".
+        let boundary = if unit.ends_with(|c: char| c.is_alphanumeric()) {
+            r"\b"
+        } else {
+            ""
+        };
+        let pattern = format!(
+            r"(?P<number>{})(?P<unit>\s*(?P<prefix>{})?{}{})?",
+            number_pattern,
+            TEXT_PREFIXES,
+            regex::escape(unit),
+            boundary,
+        );
+
+        Ok(SiUnit {
+            unit: unit.to_string(),
+            text_re: Regex::new(&pattern)?,
+            bytes_re: regex::bytes::Regex::new(&pattern)?,
+        })
+    }
+
+    /// Format `number` (in the base unit) with the most natural prefix,
+    /// to three significant figures, e.g. 12.5 µs.
+    pub fn format(&self, number: f64) -> String {
+        if number == 0.0 || !number.is_finite() {
+            return format!("{} {}", number.abs(), self.unit);
+        }
+
+        let exponent = number.abs().log10().floor() as i32;
+        let mut index = PREFIXES
+            .iter()
+            .rposition(|(_, power)| *power <= exponent)
+            .unwrap_or(0);
+
+        let round_scaled = |index: usize| {
+            let scaled = number / 10f64.powi(PREFIXES[index].1);
+            let digits = scaled.abs().log10().floor() as i32;
+            let precision = 10f64.powi(2 - digits);
+            (scaled * precision).round() / precision
+        };
+
+        // 999,999 Hz should be 1 MHz rather than 1000 kHz.
+        let mut scaled = round_scaled(index);
+        if scaled.abs() >= 1000.0 && index + 1 < PREFIXES.len() {
+            index += 1;
+            scaled = round_scaled(index);
+        }
+
+        format!("{} {}{}", scaled, PREFIXES[index].0, self.unit)
+    }
+}
+
+/// The multiplier for an SI prefix found in text, e.g. 1e-3 for "m".
+/// "u" is accepted for micro, as it's easier to type than "µ", as is
+/// the Greek letter mu.
+pub fn prefix_multiplier(prefix: &str) -> f64 {
+    let prefix = match prefix {
+        "u" | "μ" => "µ",
+        prefix => prefix,
+    };
+    PREFIXES
+        .iter()
+        .find(|(name, _)| *name == prefix)
+        .map_or(1.0, |(_, power)| 10f64.powi(*power))
+}
@@ -0,0 +1,127 @@
+//! Output templates for --format, such as "{grouped} {desc}".
+
+use std::str::FromStr;
+
+/// A value that can be included in a template.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// The number as parsed, without any formatting
+    Value,
+    /// The number with thousands separators, rounded to 2 decimal places
+    Grouped,
+    /// A short form such as 1.2M
+    Compact,
+    /// The number in binary units, such as 1.50 KiB
+    Bytes,
+    /// The size description, without parentheses
+    Desc,
+    /// "(rounded)" if rounding occurred, otherwise empty
+    Rounded,
+    /// Scientific notation, such as 1.23e6
+    Sci,
+    /// The number spelled out in words
+    Words,
+}
+
+impl FromStr for Field {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "value" => Ok(Field::Value),
+            "grouped" => Ok(Field::Grouped),
+            "compact" => Ok(Field::Compact),
+            "bytes" => Ok(Field::Bytes),
+            "desc" => Ok(Field::Desc),
+            "rounded" => Ok(Field::Rounded),
+            "sci" => Ok(Field::Sci),
+            "words" => Ok(Field::Words),
+            _ => Err(format!("unknown placeholder '{{{}}}'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Clone, Debug)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Fill in the template, using `field_value` to render each field.
+    pub fn render(&self, mut field_value: impl FnMut(Field) -> String) -> String {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Field(field) => output.push_str(&field_value(*field)),
+            }
+        }
+        output
+    }
+}
+
+/// Parse a template such as `{grouped}\t{desc}`. Use `{{` and `}}` for
+/// literal braces. `\t`, `\n` and `\\` are also recognised, as they're
+/// awkward to write in a shell.
+pub fn parse_template(src: &str) -> Result<Template, String> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = src.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder '{{{}'", name)),
+                    }
+                }
+
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(name.trim().parse()?));
+            }
+            '}' => return Err("unmatched '}' (use '}}' for a literal brace)".to_string()),
+            '\\' => match chars.peek() {
+                Some('t') => {
+                    chars.next();
+                    literal.push('\t');
+                }
+                Some('n') => {
+                    chars.next();
+                    literal.push('\n');
+                }
+                Some('\\') => {
+                    chars.next();
+                    literal.push('\\');
+                }
+                _ => literal.push('\\'),
+            },
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(Template { segments })
+}
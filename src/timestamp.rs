@@ -0,0 +1,279 @@
+//! Unix timestamps, such as 1700000000, shown as ISO-8601 dates for
+//! --timestamps.
+
+use crate::digits;
+use crate::i18n::{self, Lang};
+use crate::kinds::{DAY, HOUR, MINUTE, MONTH, WEEK, YEAR};
+use regex::Regex;
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Timestamps are only recognised between 2000 and 2100, as other
+/// values are more likely to be plain numbers.
+const EARLIEST: i128 = 946_684_800;
+const LATEST: i128 = 4_102_444_800;
+
+/// A point in time read from a Unix timestamp.
+#[derive(Clone, Copy, Debug)]
+pub struct Timestamp {
+    /// Nanoseconds since 1970-01-01T00:00:00Z.
+    nanos: i128,
+    /// How many decimal places of seconds to show, according to the
+    /// precision of the input: 3 for milliseconds, 9 for nanoseconds.
+    decimals: usize,
+}
+
+/// Settings for --timestamps.
+#[derive(Clone, Copy, Debug)]
+pub struct TimestampFormat {
+    /// The UTC offset to show dates in, in minutes.
+    pub offset_minutes: i32,
+    /// The time that relative descriptions are relative to, in
+    /// nanoseconds since the epoch.
+    pub now: i128,
+}
+
+impl TimestampFormat {
+    /// Format `timestamp` as ISO-8601, e.g. 2023-11-14T22:13:20Z.
+    pub fn format(&self, timestamp: Timestamp) -> String {
+        let local = timestamp.nanos + i128::from(self.offset_minutes) * 60 * NANOS_PER_SECOND;
+        let seconds = local.div_euclid(NANOS_PER_SECOND);
+        let nanos = local.rem_euclid(NANOS_PER_SECOND);
+
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let time_of_day = seconds.rem_euclid(86_400);
+        let mut formatted = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            time_of_day / 3600,
+            time_of_day / 60 % 60,
+            time_of_day % 60
+        );
+        if timestamp.decimals > 0 {
+            formatted.push('.');
+            formatted.push_str(&format!("{:09}", nanos)[..timestamp.decimals]);
+        }
+        formatted.push_str(&format_offset(self.offset_minutes));
+        formatted
+    }
+
+    /// Describe `timestamp` relative to `self.now`, e.g. "3 days ago" or
+    /// "in 2 hours".
+    pub fn describe(&self, timestamp: Timestamp, lang: Lang) -> String {
+        let seconds = (timestamp.nanos - self.now) as f64 / NANOS_PER_SECOND as f64;
+        if seconds.abs() < 1.0 {
+            return i18n::translate(lang, "just now").to_string();
+        }
+
+        let units = [
+            (YEAR, "year"),
+            (MONTH, "month"),
+            (WEEK, "week"),
+            (DAY, "day"),
+            (HOUR, "hour"),
+            (MINUTE, "minute"),
+            (1.0, "second"),
+        ];
+        let (size, name) = units
+            .iter()
+            .find(|(size, _)| seconds.abs() >= *size)
+            .copied()
+            .unwrap_or((1.0, "second"));
+
+        let count = (seconds.abs() / size).floor() as i64;
+        let plural = if count == 1 { "" } else { "s" };
+        let message = if seconds < 0.0 {
+            format!("{{}} {}{} ago", name, plural)
+        } else {
+            format!("in {{}} {}{}", name, plural)
+        };
+        i18n::translate_with(lang, &message, &count.to_string())
+    }
+}
+
+/// Parse `text` as a Unix timestamp in seconds, milliseconds,
+/// microseconds or nanoseconds, which is told apart by its size. Seconds
+/// may have a fractional part, as from Python's `time.time()`.
+pub fn parse_timestamp(text: &str) -> Option<Timestamp> {
+    let text = digits::to_ascii(text.trim());
+    let (whole, fraction) = match text.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (text.as_ref(), None),
+    };
+    if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let whole: i128 = whole.parse().ok()?;
+
+    let Some(fraction) = fraction else {
+        let units = [(1, 0), (1_000, 3), (1_000_000, 6), (1_000_000_000, 9)];
+        return units.iter().find_map(|(per_second, decimals)| {
+            (EARLIEST * per_second..LATEST * per_second)
+                .contains(&whole)
+                .then_some(Timestamp {
+                    nanos: whole * (NANOS_PER_SECOND / per_second),
+                    decimals: *decimals,
+                })
+        });
+    };
+
+    if fraction.is_empty()
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+        || !(EARLIEST..LATEST).contains(&whole)
+    {
+        return None;
+    }
+    // Show milliseconds, microseconds or nanoseconds, whichever covers
+    // the digits given.
+    let decimals = fraction.len().div_ceil(3).min(3) * 3;
+    let fraction = &fraction[..fraction.len().min(9)];
+    let nanos: i128 = format!("{:0<9}", fraction).parse().ok()?;
+    Some(Timestamp {
+        nanos: whole * NANOS_PER_SECOND + nanos,
+        decimals,
+    })
+}
+
+/// The current time, in nanoseconds since the epoch.
+pub fn current_time() -> i128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as i128)
+}
+
+/// Format a UTC offset in minutes, e.g. "+05:30", or "Z" for UTC.
+fn format_offset(offset_minutes: i32) -> String {
+    if offset_minutes == 0 {
+        return "Z".to_string();
+    }
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let offset = offset_minutes.abs();
+    format!("{}{:02}:{:02}", sign, offset / 60, offset % 60)
+}
+
+static OFFSET_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([+-])([0-9]{2})(?::?([0-9]{2}))?$").unwrap());
+
+/// Parse a UTC offset such as "+05:30", "-0800", "+01" or "Z", returning
+/// it in minutes.
+pub fn parse_utc_offset(src: &str) -> Result<i32, String> {
+    if src == "Z" || src.eq_ignore_ascii_case("utc") {
+        return Ok(0);
+    }
+
+    let invalid = || format!("invalid UTC offset '{}', expected e.g. +05:30 or Z", src);
+    let caps = OFFSET_RE.captures(src).ok_or_else(invalid)?;
+    let hours: i32 = caps[2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = caps
+        .get(3)
+        .map_or(Ok(0), |m| m.as_str().parse())
+        .map_err(|_| invalid())?;
+    if hours > 14 || minutes >= 60 {
+        return Err(invalid());
+    }
+
+    let offset = hours * 60 + minutes;
+    Ok(if &caps[1] == "-" { -offset } else { offset })
+}
+
+static ISO_8601_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^([0-9]{4})-([0-9]{2})-([0-9]{2})",
+        r"(?:[T ]([0-9]{2}):([0-9]{2})(?::([0-9]{2})(?:\.([0-9]{1,9}))?)?)?",
+        r"(Z|[+-][0-9]{2}(?::?[0-9]{2})?)?$",
+    ))
+    .unwrap()
+});
+
+/// Parse the time given with --now, either as a Unix timestamp in
+/// seconds or an ISO-8601 date such as 2023-11-17T22:13:20Z, returning
+/// it in nanoseconds since the epoch. Dates without an offset are UTC.
+pub fn parse_now(src: &str) -> Result<i128, String> {
+    let invalid = || {
+        format!(
+            "invalid time '{}', expected a Unix timestamp or e.g. 2023-11-17T22:13:20Z",
+            src
+        )
+    };
+
+    if let Ok(seconds) = src.parse::<f64>() {
+        // Only years that can be written as ISO-8601 dates, 0000 to 9999.
+        let earliest = days_from_civil(0, 1, 1) * 86_400;
+        let latest = days_from_civil(10_000, 1, 1) * 86_400;
+        if !(earliest as f64..latest as f64).contains(&seconds) {
+            return Err(invalid());
+        }
+        return Ok((seconds * NANOS_PER_SECOND as f64) as i128);
+    }
+
+    let caps = ISO_8601_RE.captures(src).ok_or_else(invalid)?;
+    let field = |i: usize| -> i128 { caps.get(i).map_or(0, |m| m.as_str().parse().unwrap_or(0)) };
+    let (year, month, day) = (field(1), field(2), field(3));
+    let (hour, minute, second) = (field(4), field(5), field(6));
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(invalid());
+    }
+    let nanos: i128 = caps
+        .get(7)
+        .map_or(0, |m| format!("{:0<9}", m.as_str()).parse().unwrap_or(0));
+    let offset_minutes = match caps.get(8) {
+        Some(offset) => parse_utc_offset(offset.as_str())?,
+        None => 0,
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+        - i128::from(offset_minutes) * 60;
+    Ok(seconds * NANOS_PER_SECOND + nanos)
+}
+
+/// The number of days in `month` of `year`.
+fn days_in_month(year: i128, month: i128) -> i128 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The year, month and day of `days` since 1970-01-01, using Howard
+/// Hinnant's algorithm for the proleptic Gregorian calendar.
+fn civil_from_days(days: i128) -> (i128, i128, i128) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months are counted from March, so leap days come last.
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+    (year, month, day)
+}
+
+/// The number of days from 1970-01-01 to the given date. The inverse of
+/// `civil_from_days`.
+fn days_from_civil(year: i128, month: i128, day: i128) -> i128 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
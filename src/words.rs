@@ -0,0 +1,708 @@
+//! Spelling numbers out in words, e.g. "forty-two" or "zweiundvierzig".
+
+use crate::i18n::{self, Lang};
+use clap::ValueEnum;
+
+/// How to name large powers of ten.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Scale {
+    /// Each name is 1,000 times the previous one: a billion is 10^9
+    Short,
+    /// Each name is 1,000,000 times the previous one: a billion is
+    /// 10^12, and 10^9 is a thousand million
+    Long,
+    /// Lakhs (10^5) and crores (10^7): 10^9 is a hundred crore
+    Indian,
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The names of 10^6, 10^9 etc in the short scale, or 10^6, 10^12 etc in
+/// the long scale.
+const ILLIONS: [&str; 20] = [
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+    "duodecillion",
+    "tredecillion",
+    "quattuordecillion",
+    "quindecillion",
+    "sexdecillion",
+    "septendecillion",
+    "octodecillion",
+    "novemdecillion",
+    "vigintillion",
+];
+
+/// Spell out `number` in `lang`, rounded to 2 decimal places, e.g.
+/// "minus forty-two point one two". `scale` only applies to English, as
+/// other languages have their own conventions.
+pub fn number_to_words(number: f64, scale: Scale, lang: Lang) -> String {
+    if number.is_nan() {
+        return i18n::translate(lang, "not a number").to_string();
+    }
+
+    // Round the same way as format_number_with_separators, then work
+    // from the formatted digits.
+    let rounded = crate::round_to_hundredths(number).abs();
+
+    let mut words = vec![];
+    // Don't say "minus zero" for small negative numbers.
+    if number < 0.0 && rounded != 0.0 {
+        words.push(i18n::translate(lang, "minus").to_string());
+    }
+
+    if rounded.is_infinite() {
+        words.push(i18n::translate(lang, "infinity").to_string());
+        return join_words(&words, lang);
+    }
+
+    let formatted = format!("{:.2}", rounded);
+    let (integer_digits, decimal_digits) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let decimal_digits = decimal_digits.trim_end_matches('0');
+
+    let integer_words = match lang {
+        Lang::En => integer_to_words(integer_digits, scale),
+        Lang::De => german::integer_to_words(&digit_groups(integer_digits, 3)),
+        Lang::Fr => french::integer_to_words(&digit_groups(integer_digits, 3)),
+        Lang::Es => spanish::integer_to_words(&digit_groups(integer_digits, 3)),
+        Lang::Ja => japanese::integer_to_words(&digit_groups(integer_digits, 4)),
+    };
+    match integer_words {
+        Some(integer_words) => words.push(integer_words),
+        None => {
+            // Too big to name, e.g. "one point two times ten to the power
+            // of seventy".
+            let sci = format!("{:.2e}", rounded);
+            let (mantissa, exponent) = sci.split_once('e').unwrap_or((&sci, "0"));
+            let mantissa = mantissa.parse::<f64>().unwrap_or(0.0);
+            let exponent = exponent.parse::<f64>().unwrap_or(0.0);
+
+            words.push(
+                i18n::translate(lang, "{mantissa} times ten to the power of {exponent}")
+                    .replace("{mantissa}", &number_to_words(mantissa, scale, lang))
+                    .replace("{exponent}", &number_to_words(exponent, scale, lang)),
+            );
+            return join_words(&words, lang);
+        }
+    }
+
+    if !decimal_digits.is_empty() {
+        words.push(i18n::translate(lang, "point").to_string());
+        words.extend(
+            decimal_digits
+                .chars()
+                .map(|digit| digit_to_word(digit, lang)),
+        );
+    }
+
+    join_words(&words, lang)
+}
+
+/// Japanese is written without spaces between words.
+fn join_words(words: &[String], lang: Lang) -> String {
+    match lang {
+        Lang::Ja => words.concat(),
+        _ => words.join(" "),
+    }
+}
+
+fn digit_to_word(digit: char, lang: Lang) -> String {
+    let index = digit.to_digit(10).unwrap_or(0) as usize;
+    match lang {
+        Lang::En => ONES[index],
+        Lang::De => german::ONES[index],
+        Lang::Fr => french::ONES[index],
+        Lang::Es => spanish::ONES[index],
+        Lang::Ja => japanese::DECIMAL_DIGITS[index],
+    }
+    .to_string()
+}
+
+/// Split a string of ASCII digits into groups of `size` digits, least
+/// significant first, e.g. "1234567" in threes is [567, 234, 1].
+fn digit_groups(digits: &str, size: usize) -> Vec<usize> {
+    digits
+        .as_bytes()
+        .rchunks(size)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |acc, digit| acc * 10 + (digit - b'0') as usize)
+        })
+        .collect()
+}
+
+/// Spell out a whole number given as a string of ASCII digits. Returns
+/// `None` if the number is too large to have a name.
+fn integer_to_words(digits: &str, scale: Scale) -> Option<String> {
+    let groups = digit_groups(digits, 3);
+
+    if groups.iter().all(|group| *group == 0) {
+        return Some(ONES[0].to_string());
+    }
+
+    let mut words = vec![];
+    match scale {
+        Scale::Short => {
+            // Groups are thousands, millions, billions...
+            if groups.len() > ILLIONS.len() + 2 {
+                return None;
+            }
+
+            for (power, group) in groups.iter().enumerate().rev() {
+                if *group == 0 {
+                    continue;
+                }
+
+                words.push(below_thousand_to_words(*group));
+                match power {
+                    0 => {}
+                    1 => words.push("thousand".to_string()),
+                    _ => words.push(ILLIONS[power - 2].to_string()),
+                }
+            }
+        }
+        Scale::Long => {
+            // Pairs of groups are units, millions, billions..., with each
+            // pair read as e.g. "five thousand three hundred million".
+            let pairs: Vec<&[usize]> = groups.chunks(2).collect();
+            if pairs.len() > ILLIONS.len() + 1 {
+                return None;
+            }
+
+            for (power, pair) in pairs.iter().enumerate().rev() {
+                let low = pair[0];
+                let high = pair.get(1).copied().unwrap_or(0);
+                if low == 0 && high == 0 {
+                    continue;
+                }
+
+                if high > 0 {
+                    words.push(below_thousand_to_words(high));
+                    words.push("thousand".to_string());
+                }
+                if low > 0 {
+                    words.push(below_thousand_to_words(low));
+                }
+                if power > 0 {
+                    words.push(ILLIONS[power - 1].to_string());
+                }
+            }
+        }
+        Scale::Indian => {
+            // The last three digits, then two digits each of thousands
+            // and lakhs. Everything above that is a number of crores,
+            // e.g. "one lakh crore".
+            if digits.len() > 19 {
+                return None;
+            }
+            let (crores, rest) = digits.split_at(digits.len().saturating_sub(7));
+            if crores.bytes().any(|digit| digit != b'0') {
+                words.push(integer_to_words(crores, scale)?);
+                words.push("crore".to_string());
+            }
+
+            let rest: usize = rest.parse().unwrap_or(0);
+            for (size, name) in [(100_000, "lakh"), (1_000, "thousand")] {
+                let count = rest / size % 100;
+                if count > 0 {
+                    words.push(below_thousand_to_words(count));
+                    words.push(name.to_string());
+                }
+            }
+            let below_thousand = rest % 1000;
+            if below_thousand > 0 {
+                words.push(below_thousand_to_words(below_thousand));
+            }
+        }
+    }
+
+    Some(words.join(" "))
+}
+
+/// Spell out a number from 1 to 999, e.g. "five hundred sixty-seven".
+fn below_thousand_to_words(number: usize) -> String {
+    let mut words = vec![];
+
+    let hundreds = number / 100;
+    if hundreds > 0 {
+        words.push(format!("{} hundred", ONES[hundreds]));
+    }
+
+    let rest = number % 100;
+    if rest >= 20 {
+        let ones = rest % 10;
+        if ones == 0 {
+            words.push(TENS[rest / 10].to_string());
+        } else {
+            words.push(format!("{}-{}", TENS[rest / 10], ONES[ones]));
+        }
+    } else if rest > 0 {
+        words.push(ONES[rest].to_string());
+    }
+
+    words.join(" ")
+}
+
+mod german {
+    pub const ONES: [&str; 20] = [
+        "null",
+        "eins",
+        "zwei",
+        "drei",
+        "vier",
+        "fünf",
+        "sechs",
+        "sieben",
+        "acht",
+        "neun",
+        "zehn",
+        "elf",
+        "zwölf",
+        "dreizehn",
+        "vierzehn",
+        "fünfzehn",
+        "sechzehn",
+        "siebzehn",
+        "achtzehn",
+        "neunzehn",
+    ];
+
+    const TENS: [&str; 10] = [
+        "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig",
+        "neunzig",
+    ];
+
+    /// The singular and plural names of 10^6, 10^9, 10^12 etc. German
+    /// uses the long scale, with -iarde names in between.
+    const ILLIONS: [(&str, &str); 8] = [
+        ("Million", "Millionen"),
+        ("Milliarde", "Milliarden"),
+        ("Billion", "Billionen"),
+        ("Billiarde", "Billiarden"),
+        ("Trillion", "Trillionen"),
+        ("Trilliarde", "Trilliarden"),
+        ("Quadrillion", "Quadrillionen"),
+        ("Quadrilliarde", "Quadrilliarden"),
+    ];
+
+    /// Spell out a whole number given as groups of three digits, e.g.
+    /// "eine Million zweihunderttausend".
+    pub fn integer_to_words(groups: &[usize]) -> Option<String> {
+        if groups.iter().all(|group| *group == 0) {
+            return Some(ONES[0].to_string());
+        }
+        if groups.len() > ILLIONS.len() + 2 {
+            return None;
+        }
+
+        let mut words = vec![];
+        for (power, group) in groups.iter().enumerate().skip(2).rev() {
+            let (singular, plural) = ILLIONS[power - 2];
+            match *group {
+                0 => {}
+                1 => words.push(format!("eine {}", singular)),
+                group => words.push(format!("{} {}", below_thousand(group, false), plural)),
+            }
+        }
+
+        // Numbers below a million are written as a single word.
+        let thousands = groups.get(1).copied().unwrap_or(0);
+        let mut word = String::new();
+        if thousands > 0 {
+            word.push_str(&below_thousand(thousands, false));
+            word.push_str("tausend");
+        }
+        if groups[0] > 0 {
+            word.push_str(&below_thousand(groups[0], true));
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+
+        Some(words.join(" "))
+    }
+
+    /// Spell out a number from 1 to 999, e.g. "fünfhundertsiebenundsechzig".
+    /// One is "eins" at the end of a number, and "ein" elsewhere.
+    fn below_thousand(number: usize, last: bool) -> String {
+        let mut word = String::new();
+
+        let hundreds = number / 100;
+        if hundreds > 0 {
+            word.push_str(if hundreds == 1 { "ein" } else { ONES[hundreds] });
+            word.push_str("hundert");
+        }
+
+        let rest = number % 100;
+        let ones = rest % 10;
+        if rest == 1 {
+            word.push_str(if last { "eins" } else { "ein" });
+        } else if rest >= 20 {
+            // Ones come before tens: "einundzwanzig".
+            if ones == 1 {
+                word.push_str("einund");
+            } else if ones > 0 {
+                word.push_str(ONES[ones]);
+                word.push_str("und");
+            }
+            word.push_str(TENS[rest / 10]);
+        } else if rest > 0 {
+            word.push_str(ONES[rest]);
+        }
+
+        word
+    }
+}
+
+mod french {
+    pub const ONES: [&str; 17] = [
+        "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix",
+        "onze", "douze", "treize", "quatorze", "quinze", "seize",
+    ];
+
+    /// Seventy and ninety are counted on from sixty and eighty.
+    const TENS: [&str; 10] = [
+        "",
+        "",
+        "vingt",
+        "trente",
+        "quarante",
+        "cinquante",
+        "soixante",
+        "soixante",
+        "quatre-vingt",
+        "quatre-vingt",
+    ];
+
+    /// The singular and plural names of 10^6, 10^9, 10^12 etc, in the
+    /// long scale.
+    const ILLIONS: [(&str, &str); 8] = [
+        ("million", "millions"),
+        ("milliard", "milliards"),
+        ("billion", "billions"),
+        ("billiard", "billiards"),
+        ("trillion", "trillions"),
+        ("trilliard", "trilliards"),
+        ("quadrillion", "quadrillions"),
+        ("quadrilliard", "quadrilliards"),
+    ];
+
+    /// Spell out a whole number given as groups of three digits, e.g.
+    /// "deux millions trois cent mille".
+    pub fn integer_to_words(groups: &[usize]) -> Option<String> {
+        if groups.iter().all(|group| *group == 0) {
+            return Some(ONES[0].to_string());
+        }
+        if groups.len() > ILLIONS.len() + 2 {
+            return None;
+        }
+
+        let mut words = vec![];
+        for (power, group) in groups.iter().enumerate().rev() {
+            match (power, *group) {
+                (_, 0) => {}
+                (0, group) => words.push(below_thousand(group, false)),
+                // A thousand is just "mille", not "un mille".
+                (1, 1) => words.push("mille".to_string()),
+                (1, group) => words.push(format!("{} mille", below_thousand(group, true))),
+                (power, group) => {
+                    let (singular, plural) = ILLIONS[power - 2];
+                    let name = if group == 1 { singular } else { plural };
+                    words.push(format!("{} {}", below_thousand(group, false), name));
+                }
+            }
+        }
+
+        Some(words.join(" "))
+    }
+
+    /// Spell out a number from 1 to 999, e.g. "cinq cent soixante-sept".
+    /// "cents" and "quatre-vingts" lose their plural s before "mille".
+    fn below_thousand(number: usize, before_mille: bool) -> String {
+        let mut words = vec![];
+
+        let hundreds = number / 100;
+        let rest = number % 100;
+        if hundreds == 1 {
+            words.push("cent".to_string());
+        } else if hundreds > 1 {
+            let plural = if rest == 0 && !before_mille { "s" } else { "" };
+            words.push(format!("{} cent{}", ONES[hundreds], plural));
+        }
+
+        if rest > 0 {
+            words.push(below_hundred(rest, before_mille));
+        }
+
+        words.join(" ")
+    }
+
+    fn below_hundred(number: usize, before_mille: bool) -> String {
+        if number < ONES.len() {
+            return ONES[number].to_string();
+        }
+        if number < 20 {
+            return format!("dix-{}", ONES[number - 10]);
+        }
+
+        let tens = number / 10;
+        let ones = number % 10;
+        match (tens, ones) {
+            (8, 0) if before_mille => "quatre-vingt".to_string(),
+            (8, 0) => "quatre-vingts".to_string(),
+            (7, 1) => "soixante et onze".to_string(),
+            (7 | 9, _) => format!("{}-{}", TENS[tens], below_hundred(10 + ones, before_mille)),
+            (_, 0) => TENS[tens].to_string(),
+            (8, _) => format!("{}-{}", TENS[tens], ONES[ones]),
+            (_, 1) => format!("{} et un", TENS[tens]),
+            _ => format!("{}-{}", TENS[tens], ONES[ones]),
+        }
+    }
+}
+
+mod spanish {
+    pub const ONES: [&str; 30] = [
+        "cero",
+        "uno",
+        "dos",
+        "tres",
+        "cuatro",
+        "cinco",
+        "seis",
+        "siete",
+        "ocho",
+        "nueve",
+        "diez",
+        "once",
+        "doce",
+        "trece",
+        "catorce",
+        "quince",
+        "dieciséis",
+        "diecisiete",
+        "dieciocho",
+        "diecinueve",
+        "veinte",
+        "veintiuno",
+        "veintidós",
+        "veintitrés",
+        "veinticuatro",
+        "veinticinco",
+        "veintiséis",
+        "veintisiete",
+        "veintiocho",
+        "veintinueve",
+    ];
+
+    const TENS: [&str; 10] = [
+        "",
+        "",
+        "",
+        "treinta",
+        "cuarenta",
+        "cincuenta",
+        "sesenta",
+        "setenta",
+        "ochenta",
+        "noventa",
+    ];
+
+    const HUNDREDS: [&str; 10] = [
+        "",
+        "ciento",
+        "doscientos",
+        "trescientos",
+        "cuatrocientos",
+        "quinientos",
+        "seiscientos",
+        "setecientos",
+        "ochocientos",
+        "novecientos",
+    ];
+
+    /// The singular and plural names of 10^6, 10^12, 10^18 etc. Spanish
+    /// uses the long scale, so 10^9 is "mil millones".
+    const ILLIONS: [(&str, &str); 4] = [
+        ("millón", "millones"),
+        ("billón", "billones"),
+        ("trillón", "trillones"),
+        ("cuatrillón", "cuatrillones"),
+    ];
+
+    /// Spell out a whole number given as groups of three digits, e.g.
+    /// "dos millones trescientos mil".
+    pub fn integer_to_words(groups: &[usize]) -> Option<String> {
+        if groups.iter().all(|group| *group == 0) {
+            return Some(ONES[0].to_string());
+        }
+
+        // Pairs of groups are units, millions, billones..., like the
+        // English long scale.
+        let pairs: Vec<&[usize]> = groups.chunks(2).collect();
+        if pairs.len() > ILLIONS.len() + 1 {
+            return None;
+        }
+
+        let mut words = vec![];
+        for (power, pair) in pairs.iter().enumerate().rev() {
+            let low = pair[0];
+            let high = pair.get(1).copied().unwrap_or(0);
+            if low == 0 && high == 0 {
+                continue;
+            }
+
+            // A thousand is just "mil", not "un mil".
+            if high == 1 {
+                words.push("mil".to_string());
+            } else if high > 1 {
+                words.push(format!("{} mil", below_thousand(high, true)));
+            }
+            if low > 0 {
+                words.push(below_thousand(low, power > 0));
+            }
+            if power > 0 {
+                let (singular, plural) = ILLIONS[power - 1];
+                words.push(
+                    if high == 0 && low == 1 {
+                        singular
+                    } else {
+                        plural
+                    }
+                    .to_string(),
+                );
+            }
+        }
+
+        Some(words.join(" "))
+    }
+
+    /// Spell out a number from 1 to 999, e.g. "quinientos sesenta y
+    /// siete". Before a noun such as "mil", one is shortened to "un".
+    fn below_thousand(number: usize, before_noun: bool) -> String {
+        if number == 100 {
+            return "cien".to_string();
+        }
+
+        let mut words = vec![];
+        let hundreds = number / 100;
+        if hundreds > 0 {
+            words.push(HUNDREDS[hundreds].to_string());
+        }
+
+        let rest = number % 100;
+        if rest > 0 {
+            words.push(below_hundred(rest, before_noun));
+        }
+
+        words.join(" ")
+    }
+
+    fn below_hundred(number: usize, before_noun: bool) -> String {
+        match number {
+            1 if before_noun => "un".to_string(),
+            21 if before_noun => "veintiún".to_string(),
+            number if number < ONES.len() => ONES[number].to_string(),
+            number => {
+                let tens = TENS[number / 10];
+                match number % 10 {
+                    0 => tens.to_string(),
+                    1 if before_noun => format!("{} y un", tens),
+                    ones => format!("{} y {}", tens, ONES[ones]),
+                }
+            }
+        }
+    }
+}
+
+mod japanese {
+    const DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+    /// Digits after the decimal point, where zero is written 〇.
+    pub const DECIMAL_DIGITS: [&str; 10] =
+        ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+    /// The names of 10^4, 10^8, 10^12 etc.
+    const MYRIADS: [&str; 13] = [
+        "", "万", "億", "兆", "京", "垓", "秭", "穣", "溝", "澗", "正", "載", "極",
+    ];
+
+    /// Spell out a whole number given as groups of four digits, e.g.
+    /// "一万二千三百四十五".
+    pub fn integer_to_words(groups: &[usize]) -> Option<String> {
+        if groups.iter().all(|group| *group == 0) {
+            return Some(DIGITS[0].to_string());
+        }
+        if groups.len() > MYRIADS.len() {
+            return None;
+        }
+
+        let mut words = String::new();
+        for (power, group) in groups.iter().enumerate().rev() {
+            if *group > 0 {
+                words.push_str(&below_myriad(*group, words.is_empty()));
+                words.push_str(MYRIADS[power]);
+            }
+        }
+
+        Some(words)
+    }
+
+    /// Spell out a number from 1 to 9,999, e.g. "五千六百七十八". A one
+    /// before 百 or 十 isn't written, and nor is one before 千 at the
+    /// start of a number: 千 but 二万一千.
+    fn below_myriad(number: usize, leading: bool) -> String {
+        let mut words = String::new();
+        for (size, name) in [(1000, "千"), (100, "百"), (10, "十")] {
+            match number / size % 10 {
+                0 => {}
+                1 if size < 1000 || leading => words.push_str(name),
+                digit => {
+                    words.push_str(DIGITS[digit]);
+                    words.push_str(name);
+                }
+            }
+        }
+
+        let ones = number % 10;
+        if ones > 0 {
+            words.push_str(DIGITS[ones]);
+        }
+
+        words
+    }
+}
@@ -1,9 +1,30 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
 
+/// A command to run nn, with the locale variables cleared and an empty
+/// config directory, so the output doesn't depend on the environment the
+/// tests are run in. Tests about locales or config files set them
+/// explicitly.
+fn nn() -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    cmd.env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .env("XDG_CONFIG_HOME", empty_config_dir());
+    cmd
+}
+
+/// An empty directory to use as `XDG_CONFIG_HOME`, so the user's own
+/// `~/.config/nn` files aren't read.
+fn empty_config_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("nn-empty-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
 #[test]
 fn test_small_integer() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("42")
         .assert()
         .success()
@@ -13,7 +34,7 @@ fn test_small_integer() {
 
 #[test]
 fn test_small_decimal() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("42.50")
         .assert()
         .success()
@@ -23,7 +44,7 @@ fn test_small_decimal() {
 
 #[test]
 fn test_decimal_with_rounding() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("42.123456")
         .assert()
         .success()
@@ -34,7 +55,7 @@ fn test_decimal_with_rounding() {
 
 #[test]
 fn test_medium_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("5000")
         .assert()
         .success()
@@ -44,7 +65,7 @@ fn test_medium_number() {
 
 #[test]
 fn test_pretty_big_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1234567")
         .assert()
         .success()
@@ -54,7 +75,7 @@ fn test_pretty_big_number() {
 
 #[test]
 fn test_pretty_big_decimal() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1234567.89")
         .assert()
         .success()
@@ -64,7 +85,7 @@ fn test_pretty_big_decimal() {
 
 #[test]
 fn test_extremely_big_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("9876543210")
         .assert()
         .success()
@@ -74,7 +95,7 @@ fn test_extremely_big_number() {
 
 #[test]
 fn test_negative_small_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("-42")
         .assert()
         .success()
@@ -84,7 +105,7 @@ fn test_negative_small_number() {
 
 #[test]
 fn test_negative_medium_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("-5000")
         .assert()
         .success()
@@ -94,7 +115,7 @@ fn test_negative_medium_number() {
 
 #[test]
 fn test_zero() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("0")
         .assert()
         .success()
@@ -104,7 +125,7 @@ fn test_zero() {
 
 #[test]
 fn test_text_without_numbers_via_stdin() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("not a number")
         .assert()
         .success()
@@ -113,7 +134,7 @@ fn test_text_without_numbers_via_stdin() {
 
 #[test]
 fn test_empty_input() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("")
         .assert()
         .success()
@@ -122,7 +143,7 @@ fn test_empty_input() {
 
 #[test]
 fn test_help_flag() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("--help")
         .assert()
         .success()
@@ -134,7 +155,7 @@ fn test_help_flag() {
 
 #[test]
 fn test_help_short_flag() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("-h")
         .assert()
         .success()
@@ -145,7 +166,7 @@ fn test_help_short_flag() {
 
 #[test]
 fn test_version_flag() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("--version")
         .assert()
         .success()
@@ -155,7 +176,7 @@ fn test_version_flag() {
 
 #[test]
 fn test_version_short_flag() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("-V")
         .assert()
         .success()
@@ -165,7 +186,7 @@ fn test_version_short_flag() {
 
 #[test]
 fn test_decimal_no_rounding_needed() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("123.45")
         .assert()
         .success()
@@ -176,7 +197,7 @@ fn test_decimal_no_rounding_needed() {
 
 #[test]
 fn test_large_decimal_with_rounding() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("9876543210.999")
         .assert()
         .success()
@@ -187,7 +208,7 @@ fn test_large_decimal_with_rounding() {
 
 #[test]
 fn test_scientific_notation_large() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1.23e5")
         .assert()
         .success()
@@ -197,7 +218,7 @@ fn test_scientific_notation_large() {
 
 #[test]
 fn test_scientific_notation_small() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("3.14e2")
         .assert()
         .success()
@@ -207,7 +228,7 @@ fn test_scientific_notation_small() {
 
 #[test]
 fn test_scientific_notation_extremely_big() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("9.87654321e9")
         .assert()
         .success()
@@ -217,17 +238,17 @@ fn test_scientific_notation_extremely_big() {
 
 #[test]
 fn test_scientific_notation_very_small() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1.5e-3")
         .assert()
         .success()
         .stdout(predicate::str::contains("0"))
-        .stdout(predicate::str::contains("(zero)"));
+        .stdout(predicate::str::contains("(tiny)"));
 }
 
 #[test]
 fn test_scientific_notation_with_rounding() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1.234567e3")
         .assert()
         .success()
@@ -238,7 +259,7 @@ fn test_scientific_notation_with_rounding() {
 
 #[test]
 fn test_scientific_notation_negative() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("-2.5e4")
         .assert()
         .success()
@@ -250,7 +271,7 @@ fn test_scientific_notation_negative() {
 
 #[test]
 fn test_arg_small_integer() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("42")
         .assert()
         .success()
@@ -260,7 +281,7 @@ fn test_arg_small_integer() {
 
 #[test]
 fn test_arg_medium_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("5000")
         .assert()
         .success()
@@ -270,7 +291,7 @@ fn test_arg_medium_number() {
 
 #[test]
 fn test_arg_decimal_with_rounding() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("42.123456")
         .assert()
         .success()
@@ -281,7 +302,7 @@ fn test_arg_decimal_with_rounding() {
 
 #[test]
 fn test_arg_pretty_big_decimal() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1234567.89")
         .assert()
         .success()
@@ -291,7 +312,7 @@ fn test_arg_pretty_big_decimal() {
 
 #[test]
 fn test_arg_extremely_big_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("9876543210")
         .assert()
         .success()
@@ -301,7 +322,7 @@ fn test_arg_extremely_big_number() {
 
 #[test]
 fn test_arg_scientific_notation() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1.23e5")
         .assert()
         .success()
@@ -311,7 +332,7 @@ fn test_arg_scientific_notation() {
 
 #[test]
 fn test_arg_negative_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("--")
         .arg("-5000")
         .assert()
@@ -322,7 +343,7 @@ fn test_arg_negative_number() {
 
 #[test]
 fn test_arg_text_without_numbers() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("not_a_number")
         .assert()
         .success()
@@ -333,7 +354,7 @@ fn test_arg_text_without_numbers() {
 
 #[test]
 fn test_bytes_flag_kib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1024")
         .arg("--bytes")
         .assert()
@@ -343,7 +364,7 @@ fn test_bytes_flag_kib() {
 
 #[test]
 fn test_bytes_flag_mib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1048576")
         .arg("--bytes")
         .assert()
@@ -353,7 +374,7 @@ fn test_bytes_flag_mib() {
 
 #[test]
 fn test_bytes_flag_gib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1073741824")
         .arg("--bytes")
         .assert()
@@ -363,7 +384,7 @@ fn test_bytes_flag_gib() {
 
 #[test]
 fn test_bytes_flag_short() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("2048")
         .arg("-b")
         .assert()
@@ -373,7 +394,7 @@ fn test_bytes_flag_short() {
 
 #[test]
 fn test_bytes_flag_decimal() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1536.5")
         .arg("--bytes")
         .assert()
@@ -383,7 +404,7 @@ fn test_bytes_flag_decimal() {
 
 #[test]
 fn test_bytes_flag_less_than_kib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("512")
         .arg("--bytes")
         .assert()
@@ -393,7 +414,7 @@ fn test_bytes_flag_less_than_kib() {
 
 #[test]
 fn test_bytes_flag_with_stdin() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("--bytes")
         .write_stdin("2048")
         .assert()
@@ -403,7 +424,7 @@ fn test_bytes_flag_with_stdin() {
 
 #[test]
 fn test_without_bytes_flag() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1024")
         .assert()
         .success()
@@ -413,7 +434,7 @@ fn test_without_bytes_flag() {
 
 #[test]
 fn test_bytes_flag_tib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("1099511627776")
         .arg("--bytes")
         .assert()
@@ -423,7 +444,7 @@ fn test_bytes_flag_tib() {
 
 #[test]
 fn test_bytes_flag_multiple_kib() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.arg("5120")
         .arg("--bytes")
         .assert()
@@ -435,7 +456,7 @@ fn test_bytes_flag_multiple_kib() {
 
 #[test]
 fn test_text_with_single_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("The file is 1024 bytes")
         .assert()
         .success()
@@ -444,7 +465,7 @@ fn test_text_with_single_number() {
 
 #[test]
 fn test_text_with_multiple_numbers() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("I have 5000 apples and 2500 oranges")
         .assert()
         .success()
@@ -455,7 +476,7 @@ fn test_text_with_multiple_numbers() {
 
 #[test]
 fn test_text_with_decimal() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Price is 1234.56 dollars")
         .assert()
         .success()
@@ -464,7 +485,7 @@ fn test_text_with_decimal() {
 
 #[test]
 fn test_text_without_numbers() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Hello world without numbers")
         .assert()
         .success()
@@ -473,7 +494,7 @@ fn test_text_without_numbers() {
 
 #[test]
 fn test_text_with_negative_number() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Temperature: -25.5 degrees")
         .assert()
         .success()
@@ -482,7 +503,7 @@ fn test_text_with_negative_number() {
 
 #[test]
 fn test_text_with_scientific_notation() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Science: 1.23e5 particles")
         .assert()
         .success()
@@ -491,7 +512,7 @@ fn test_text_with_scientific_notation() {
 
 #[test]
 fn test_text_with_many_numbers() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Results: 10 samples, 1000 iterations, 0.05 error rate")
         .assert()
         .success()
@@ -502,7 +523,7 @@ fn test_text_with_many_numbers() {
 
 #[test]
 fn test_pure_number_still_shows_description() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("1024")
         .assert()
         .success()
@@ -512,9 +533,2353 @@ fn test_pure_number_still_shows_description() {
 
 #[test]
 fn test_text_preserves_formatting() {
-    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("nn"));
+    let mut cmd = nn();
     cmd.write_stdin("Total: 1234567 items")
         .assert()
         .success()
         .stdout(predicate::str::contains("Total: 1,234,567 items"));
 }
+
+// Tests for input that isn't valid UTF-8
+
+#[test]
+fn test_invalid_utf8_line_passes_through() {
+    let mut cmd = nn();
+    cmd.write_stdin(b"caf\xe9 costs 5000 francs\n".to_vec())
+        .assert()
+        .success()
+        .stdout(predicate::eq(&b"caf\xe9 costs 5,000 francs\n"[..]));
+}
+
+#[test]
+fn test_invalid_utf8_does_not_stop_processing() {
+    let mut cmd = nn();
+    cmd.write_stdin(b"\xff\xfe 1000\nThe file is 1024 bytes\n".to_vec())
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            &b"\xff\xfe 1,000\nThe file is 1,024 bytes\n"[..],
+        ));
+}
+
+// Tests for preserving whitespace and line endings
+
+#[test]
+fn test_text_preserves_indentation() {
+    let mut cmd = nn();
+    cmd.write_stdin("limits:\n  max_size: 5000  \n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("limits:\n  max_size: 5,000  \n"));
+}
+
+#[test]
+fn test_number_line_preserves_whitespace() {
+    let mut cmd = nn();
+    cmd.write_stdin("  42  \n\t1000\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("  42 (small)  \n\t1,000 (medium)\n"));
+}
+
+#[test]
+fn test_text_preserves_crlf() {
+    let mut cmd = nn();
+    cmd.write_stdin("Total: 1000\r\nCount: 2000\r\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("Total: 1,000\r\nCount: 2,000\r\n"));
+}
+
+#[test]
+fn test_text_preserves_missing_final_newline() {
+    let mut cmd = nn();
+    cmd.write_stdin("Total: 1000\n\t\nCount: 2000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("Total: 1,000\n\t\nCount: 2,000"));
+}
+
+// Tests for large streams
+
+#[test]
+fn test_many_lines() {
+    let input = "Total: 1000\n".repeat(100_000);
+    let expected = "Total: 1,000\n".repeat(100_000);
+
+    let mut cmd = nn();
+    cmd.write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::eq(expected));
+}
+
+#[test]
+fn test_broken_pipe_exits_quietly() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("nn"))
+        .env("XDG_CONFIG_HOME", empty_config_dir())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Close the read end of nn's stdout before it writes anything.
+    drop(child.stdout.take());
+
+    let mut stdin = child.stdin.take().unwrap();
+    let input = "Total: 1000\n".repeat(100_000);
+    // nn may exit before consuming all of its input.
+    let _ = stdin.write_all(input.as_bytes());
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+// Tests for --follow and --line-buffered
+
+/// Read a line from `lines`, failing the test rather than hanging if nn
+/// doesn't produce one.
+fn recv_line(lines: &std::sync::mpsc::Receiver<String>) -> String {
+    lines
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .expect("timed out waiting for output from nn")
+}
+
+/// Spawn nn with `args`, returning the child and a channel of its stdout
+/// lines.
+fn spawn_nn_lines(args: &[&str]) -> (std::process::Child, std::sync::mpsc::Receiver<String>) {
+    use std::io::BufRead;
+    use std::process::Stdio;
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("nn"))
+        .args(args)
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .env("XDG_CONFIG_HOME", empty_config_dir())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    (child, rx)
+}
+
+#[test]
+fn test_line_buffered_flushes_each_line() {
+    use std::io::Write;
+
+    let (mut child, lines) = spawn_nn_lines(&["--line-buffered"]);
+    let mut stdin = child.stdin.take().unwrap();
+
+    stdin.write_all(b"Total: 1000\n").unwrap();
+    stdin.flush().unwrap();
+    assert_eq!(recv_line(&lines), "Total: 1,000");
+
+    stdin.write_all(b"Total: 2000\n").unwrap();
+    stdin.flush().unwrap();
+    assert_eq!(recv_line(&lines), "Total: 2,000");
+
+    drop(stdin);
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_follow_handles_appends_and_rotation() {
+    use std::io::Write;
+
+    let dir = std::env::temp_dir().join(format!("nn-follow-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.log");
+    std::fs::write(&path, "started with 1000 workers\n").unwrap();
+
+    let (mut child, lines) = spawn_nn_lines(&["--follow", path.to_str().unwrap()]);
+    assert_eq!(recv_line(&lines), "started with 1,000 workers");
+
+    let mut log = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap();
+    writeln!(log, "handled 2000 requests").unwrap();
+    assert_eq!(recv_line(&lines), "handled 2,000 requests");
+
+    // Rotate the log: move it aside and start a new file at the same path.
+    // What was written just before the rotation is still read.
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    writeln!(log, "handled 2500 requests").unwrap();
+    std::fs::rename(&path, dir.join("app.log.1")).unwrap();
+    std::fs::write(&path, "rotated after 3000 requests\n").unwrap();
+    assert_eq!(recv_line(&lines), "handled 2,500 requests");
+    assert_eq!(recv_line(&lines), "rotated after 3,000 requests");
+
+    // Truncate the log in place.
+    std::fs::write(&path, "").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::write(&path, "truncated at 4000\n").unwrap();
+    assert_eq!(recv_line(&lines), "truncated at 4,000");
+
+    child.kill().unwrap();
+    let _ = child.wait();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_follow_waits_for_missing_file() {
+    let dir = std::env::temp_dir().join(format!("nn-follow-missing-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.log");
+
+    let (mut child, lines) = spawn_nn_lines(&["--follow", path.to_str().unwrap()]);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::write(&path, "created with 1000 workers\n").unwrap();
+    assert_eq!(recv_line(&lines), "created with 1,000 workers");
+
+    child.kill().unwrap();
+    let _ = child.wait();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_follow_unreadable_path() {
+    let mut cmd = nn();
+    cmd.arg("--follow")
+        .arg(std::env::temp_dir())
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("nn: "));
+}
+
+// Tests for --zero-terminated
+
+#[test]
+fn test_zero_terminated_records() {
+    let mut cmd = nn();
+    cmd.arg("-z")
+        .write_stdin("backup of 2000 files\0multi\nline 3000\0")
+        .assert()
+        .success()
+        .stdout(predicate::eq("backup of 2,000 files\0multi\nline 3,000\0"));
+}
+
+#[test]
+fn test_zero_terminated_pure_number() {
+    let mut cmd = nn();
+    cmd.arg("--zero-terminated")
+        .write_stdin("5000\0")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("5,000"))
+        .stdout(predicate::str::contains("(medium)"))
+        .stdout(predicate::str::ends_with("\0"));
+}
+
+// Tests for --color
+
+#[test]
+fn test_no_color_when_piped() {
+    let mut cmd = nn();
+    cmd.env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .arg("42.123")
+        .assert()
+        .success()
+        .stdout(predicate::eq("42.12 (rounded) (small)\n"));
+}
+
+#[test]
+fn test_color_always() {
+    let mut cmd = nn();
+    cmd.env("NO_COLOR", "1")
+        .arg("--color=always")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_color_never() {
+    let mut cmd = nn();
+    cmd.env("CLICOLOR_FORCE", "1")
+        .arg("--color")
+        .arg("never")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::eq("42 (small)\n"));
+}
+
+#[test]
+fn test_clicolor_force() {
+    let mut cmd = nn();
+    cmd.env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_no_color_beats_clicolor_force() {
+    let mut cmd = nn();
+    cmd.env("NO_COLOR", "1")
+        .env("CLICOLOR_FORCE", "1")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::eq("42 (small)\n"));
+}
+
+// Tests for custom size categories
+
+#[test]
+fn test_category_flags() {
+    let mut cmd = nn();
+    cmd.arg("--category")
+        .arg("100:lots:red+bold")
+        .arg("--category")
+        .arg("0:few")
+        .arg("250")
+        .assert()
+        .success()
+        .stdout(predicate::eq("250 (lots)\n"));
+}
+
+#[test]
+fn test_category_below_all_thresholds() {
+    let mut cmd = nn();
+    cmd.arg("--category=1_000:busy")
+        .arg("50")
+        .assert()
+        .success()
+        .stdout(predicate::eq("50\n"));
+}
+
+#[test]
+fn test_category_invalid() {
+    let mut cmd = nn();
+    cmd.arg("--category=lots:100")
+        .arg("50")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid threshold 'lots'"));
+}
+
+#[test]
+fn test_category_file() {
+    let path = std::env::temp_dir().join(format!("nn-categories-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "# Revenue\n0:peanuts:dimmed\n\n1e6:real money:bright_green\n",
+    )
+    .unwrap();
+
+    let mut cmd = nn();
+    cmd.arg("--category-file")
+        .arg(&path)
+        .arg("2500000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2,500,000 (real money)\n"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_default_category_file() {
+    let dir = std::env::temp_dir().join(format!("nn-config-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("nn")).unwrap();
+    std::fs::write(dir.join("nn").join("categories"), "0:some\n10:many\n").unwrap();
+
+    let mut cmd = nn();
+    cmd.env("XDG_CONFIG_HOME", &dir)
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::eq("42 (many)\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// Tests for finer-grained magnitudes
+
+#[test]
+fn test_microscopic_number() {
+    let mut cmd = nn();
+    cmd.arg("0.0001")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(microscopic)"));
+}
+
+#[test]
+fn test_tiny_number() {
+    let mut cmd = nn();
+    cmd.arg("0.25")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0.25"))
+        .stdout(predicate::str::contains("(tiny)"));
+}
+
+#[test]
+fn test_gigantic_number() {
+    let mut cmd = nn();
+    cmd.arg("4.2e13")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("42,000,000,000,000"))
+        .stdout(predicate::str::contains("(gigantic)"));
+}
+
+#[test]
+fn test_astronomical_number() {
+    let mut cmd = nn();
+    cmd.arg("2e15")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(astronomical)"));
+}
+
+#[test]
+fn test_cosmic_number() {
+    let mut cmd = nn();
+    cmd.arg("1e20")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("100,000,000,000,000,000,000"))
+        .stdout(predicate::str::contains("(cosmic)"));
+}
+
+#[test]
+fn test_cosmic_number_not_rounded() {
+    let mut cmd = nn();
+    cmd.arg("1e21")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,000,000,000,000,000,000,000 (cosmic)\n"));
+}
+
+#[test]
+fn test_number_past_i128() {
+    let mut cmd = nn();
+    cmd.arg("1e40").assert().success().stdout(predicate::eq(
+        "10,000,000,000,000,000,303,786,028,427,003,666,890,752 (cosmic)\n",
+    ));
+}
+
+#[test]
+fn test_infinity_passed_through() {
+    let mut cmd = nn();
+    cmd.arg("inf")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("inf "));
+}
+
+#[test]
+fn test_magnitude_flag() {
+    let mut cmd = nn();
+    cmd.arg("--magnitude")
+        .arg("4.2e13")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(gigantic, ~10^13)"));
+}
+
+#[test]
+fn test_magnitude_flag_fraction() {
+    let mut cmd = nn();
+    cmd.arg("-m")
+        .arg("0.0001")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(microscopic, ~10^-4)"));
+}
+
+// Tests for --kind
+
+#[test]
+fn test_bytes_flag_describes_storage() {
+    let mut cmd = nn();
+    cmd.arg("--bytes")
+        .arg("1073741824")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1 GiB (fits in RAM)\n"));
+}
+
+#[test]
+fn test_kind_bytes_without_binary_units() {
+    let mut cmd = nn();
+    cmd.arg("--kind=bytes")
+        .arg("65536")
+        .assert()
+        .success()
+        .stdout(predicate::eq("65,536 (fits in CPU cache)\n"));
+}
+
+#[test]
+fn test_kind_number_with_bytes_flag() {
+    let mut cmd = nn();
+    cmd.arg("--bytes")
+        .arg("--kind=number")
+        .arg("2048")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2 KiB (medium)\n"));
+}
+
+#[test]
+fn test_kind_duration() {
+    let mut cmd = nn();
+    cmd.arg("--kind")
+        .arg("duration")
+        .arg("259200")
+        .assert()
+        .success()
+        .stdout(predicate::eq("259,200 (about 3 days)\n"));
+}
+
+#[test]
+fn test_kind_duration_singular() {
+    let mut cmd = nn();
+    cmd.arg("-k")
+        .arg("duration")
+        .arg("3600")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(about 1 hour)"));
+}
+
+#[test]
+fn test_kind_money() {
+    let mut cmd = nn();
+    cmd.arg("--kind=money")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,234,567 (about 1.2 million dollars)\n"));
+}
+
+#[test]
+fn test_kind_money_huge() {
+    let mut cmd = nn();
+    cmd.arg("--kind=money")
+        .arg("1e30")
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "(about 1000000000000000000 trillion dollars)\n",
+        ));
+}
+
+#[test]
+fn test_kind_not_described_when_not_finite() {
+    let mut cmd = nn();
+    cmd.arg("--kind=duration")
+        .arg("inf")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("years").not());
+
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("nan")
+        .assert()
+        .success()
+        .stdout(predicate::eq("NaN\n"));
+
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("inf")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dollars").not());
+}
+
+// Tests for --compare
+
+#[test]
+fn test_compare_close_reference() {
+    let mut cmd = nn();
+    cmd.arg("--compare")
+        .arg("80000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "80,000,000 (pretty big) ≈ the population of Germany\n",
+        ));
+}
+
+#[test]
+fn test_compare_multiple_of_reference() {
+    let mut cmd = nn();
+    cmd.arg("-b")
+        .arg("-c")
+        .arg("14e9")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈ 3 × a DVD"));
+}
+
+#[test]
+fn test_compare_smaller_than_all_references() {
+    let mut cmd = nn();
+    cmd.arg("--compare")
+        .arg("--kind=duration")
+        .arg("0.25")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈ 0.25 × a heartbeat"));
+}
+
+#[test]
+fn test_compare_far_smaller_than_all_references() {
+    let mut cmd = nn();
+    cmd.arg("--compare")
+        .arg("1e-300")
+        .assert()
+        .success()
+        .stdout(predicate::eq("0 (microscopic)\n"));
+}
+
+#[test]
+fn test_compare_far_larger_than_all_references() {
+    let mut cmd = nn();
+    cmd.arg("--compare")
+        .arg("1e33")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "≈ 2.7×10^19 × the cells in a human body",
+        ));
+}
+
+#[test]
+fn test_compare_custom_reference() {
+    let mut cmd = nn();
+    cmd.arg("--compare")
+        .arg("--reference")
+        .arg("1e6:a lottery win")
+        .arg("--kind=money")
+        .arg("2000000")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈ 2 × a lottery win"));
+}
+
+#[test]
+fn test_compare_reference_file() {
+    let dir = std::env::temp_dir().join(format!("nn-references-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("nn")).unwrap();
+    std::fs::write(
+        dir.join("nn").join("references"),
+        "# Our numbers\nnumber:1_500:our daily signups\nmoney:1e6:our budget\n",
+    )
+    .unwrap();
+
+    let mut cmd = nn();
+    cmd.env("XDG_CONFIG_HOME", &dir)
+        .arg("--compare")
+        .arg("1500")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈ our daily signups"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_no_compare_by_default() {
+    let mut cmd = nn();
+    cmd.arg("80000000")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈").not());
+}
+
+// Tests for --format
+
+#[test]
+fn test_format_grouped_and_desc() {
+    let mut cmd = nn();
+    cmd.arg("--format")
+        .arg("{grouped} is {desc}")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,234,567 is pretty big\n"));
+}
+
+#[test]
+fn test_format_all_placeholders() {
+    let mut cmd = nn();
+    cmd.arg("-f")
+        .arg("{value}|{grouped}|{compact}|{bytes}|{desc}|{rounded}|{sci}|{words}")
+        .arg("1536.123")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "1536.123|1,536.12|1.5K|1.50 KiB|medium|(rounded)|1.54e3|one thousand five hundred thirty-six point one two\n",
+        ));
+}
+
+#[test]
+fn test_format_compact() {
+    let mut cmd = nn();
+    cmd.arg("--format={compact}")
+        .write_stdin("999999\n2500000000\n42\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1M\n2.5B\n42\n"));
+}
+
+#[test]
+fn test_format_escapes() {
+    let mut cmd = nn();
+    cmd.arg("--format={{{grouped}}}\\t{sci}")
+        .arg("123000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("{123,000}\t1.23e5\n"));
+}
+
+#[test]
+fn test_format_leaves_text_alone() {
+    let mut cmd = nn();
+    cmd.arg("--format={compact}")
+        .write_stdin("I have 5000 apples\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("I have 5,000 apples\n"));
+}
+
+#[test]
+fn test_format_unknown_placeholder() {
+    let mut cmd = nn();
+    cmd.arg("--format={bogus}")
+        .arg("42")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown placeholder '{bogus}'"));
+}
+
+// Tests for --json
+
+fn parse_json_lines(output: &[u8]) -> Vec<serde_json::Value> {
+    std::str::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_json_number() {
+    let mut cmd = nn();
+    let output = cmd
+        .arg("--json")
+        .arg("1536.123")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let records = parse_json_lines(&output);
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record["type"], "number");
+    assert_eq!(record["value"], 1536.123);
+    assert_eq!(record["grouped"], "1,536.12");
+    assert_eq!(record["rounded"], true);
+    assert!((record["rounding_delta"].as_f64().unwrap() + 0.003).abs() < 1e-9);
+    assert_eq!(record["category"], "medium");
+    assert_eq!(record["bytes"], "1.50 KiB");
+}
+
+#[test]
+fn test_json_category_names() {
+    let mut cmd = nn();
+    let output = cmd
+        .arg("--json")
+        .write_stdin("0\n2000000\n5e9\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let categories: Vec<_> = parse_json_lines(&output)
+        .iter()
+        .map(|record| record["category"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(categories, ["zero", "pretty_big", "extremely_big"]);
+}
+
+#[test]
+fn test_json_text_spans() {
+    let mut cmd = nn();
+    let output = cmd
+        .arg("--json")
+        .write_stdin("I have 5000 apples\n\nand 2.5e3 pears\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    // Blank lines don't produce a record.
+    let records = parse_json_lines(&output);
+    assert_eq!(records.len(), 2);
+
+    let record = &records[0];
+    assert_eq!(record["type"], "text");
+    assert_eq!(record["input"], "I have 5000 apples");
+    assert_eq!(record["output"], "I have 5,000 apples");
+    assert_eq!(
+        record["numbers"],
+        serde_json::json!([{
+            "start": 7,
+            "end": 11,
+            "text": "5000",
+            "value": 5000.0,
+            "replacement": "5,000",
+        }])
+    );
+
+    assert_eq!(records[1]["output"], "and 2,500 pears");
+}
+
+#[test]
+fn test_json_is_never_colored() {
+    let mut cmd = nn();
+    cmd.arg("--json")
+        .arg("--color=always")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+// Tests for --porcelain
+
+#[test]
+fn test_porcelain_number() {
+    let mut cmd = nn();
+    cmd.arg("--porcelain")
+        .arg("1536.123")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1536.123\t1,536.12\tmedium\t1\t1.50 KiB\n"));
+}
+
+#[test]
+fn test_porcelain_v1_stream() {
+    let mut cmd = nn();
+    cmd.arg("--porcelain=v1")
+        .arg("--color=always")
+        .write_stdin("1234567\n\nI have 5000 apples\n0\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "1234567\t1,234,567\tpretty_big\t0\t1.18 MiB\n\
+             \t\t\t\t\n\
+             \t\t\t\t\n\
+             0\t0\tzero\t0\t0 B\n",
+        ));
+}
+
+#[test]
+fn test_porcelain_unknown_version() {
+    let mut cmd = nn();
+    cmd.arg("--porcelain=v99")
+        .arg("42")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'v99'"));
+}
+
+// Tests for --words
+
+#[test]
+fn test_words() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "one million two hundred thirty-four thousand five hundred sixty-seven\n",
+        ));
+}
+
+#[test]
+fn test_words_negative_decimal() {
+    let mut cmd = nn();
+    cmd.arg("-w")
+        .arg("--")
+        .arg("-40.123")
+        .assert()
+        .success()
+        .stdout(predicate::eq("minus forty point one two\n"));
+}
+
+#[test]
+fn test_words_large_short_scale() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("5300000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("five billion three hundred million\n"));
+}
+
+#[test]
+fn test_words_large_long_scale() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("--scale=long")
+        .write_stdin("5300000000\n2e12\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "five thousand three hundred million\ntwo billion\n",
+        ));
+}
+
+#[test]
+fn test_words_too_large_to_name() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("1.5e70")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "one point five times ten to the power of seventy\n",
+        ));
+}
+
+#[test]
+fn test_words_exact_large_number() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("1e21")
+        .assert()
+        .success()
+        .stdout(predicate::eq("one sextillion\n"));
+}
+
+#[test]
+fn test_words_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .write_stdin("I have 5000 apples and 21 oranges")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "I have five thousand apples and twenty-one oranges",
+        ));
+}
+
+#[test]
+fn test_words_template_long_scale() {
+    let mut cmd = nn();
+    cmd.arg("--format={grouped} = {words}")
+        .arg("--scale=long")
+        .arg("1000000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,000,000,000 = one thousand million\n"));
+}
+
+// Tests for parsing numbers written in words and shorthand
+
+#[test]
+fn test_parse_words() {
+    let mut cmd = nn();
+    cmd.arg("two point five million")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2,500,000 (pretty big)\n"));
+}
+
+#[test]
+fn test_parse_words_hundred_and() {
+    let mut cmd = nn();
+    cmd.arg("a hundred and five")
+        .assert()
+        .success()
+        .stdout(predicate::eq("105 (small)\n"));
+}
+
+#[test]
+fn test_parse_dozen() {
+    let mut cmd = nn();
+    cmd.arg("a dozen")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12 (small)\n"));
+}
+
+#[test]
+fn test_parse_shorthand_bn() {
+    let mut cmd = nn();
+    cmd.arg("1.2bn")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,200,000,000 (extremely big)\n"));
+}
+
+#[test]
+fn test_parse_shorthand_k() {
+    let mut cmd = nn();
+    cmd.arg("3k")
+        .assert()
+        .success()
+        .stdout(predicate::eq("3,000 (medium)\n"));
+}
+
+#[test]
+fn test_parse_negative_words() {
+    let mut cmd = nn();
+    cmd.arg("minus twenty-one")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-21 (small)\n"));
+}
+
+#[test]
+fn test_parse_words_in_text() {
+    let mut cmd = nn();
+    cmd.write_stdin("We sold 2.5 million units and three thousand hats")
+        .assert()
+        .success()
+        .stdout(predicate::eq("We sold 2,500,000 units and 3,000 hats"));
+}
+
+#[test]
+fn test_parse_words_in_text_ignores_lone_words() {
+    let mut cmd = nn();
+    cmd.write_stdin("no one came, a cat sat")
+        .assert()
+        .success()
+        .stdout(predicate::eq("no one came, a cat sat"));
+}
+
+#[test]
+fn test_parse_words_in_text_keeps_separate_words() {
+    let mut cmd = nn();
+    cmd.write_stdin("I counted one two three")
+        .assert()
+        .success()
+        .stdout(predicate::eq("I counted one two three"));
+}
+
+#[test]
+fn test_parse_words_in_text_tens_then_unit() {
+    let mut cmd = nn();
+    cmd.write_stdin("ten-four good buddy, twenty-one pilots")
+        .assert()
+        .success()
+        .stdout(predicate::eq("ten-four good buddy, 21 pilots"));
+}
+
+#[test]
+fn test_parse_words_to_words() {
+    let mut cmd = nn();
+    cmd.arg("--words")
+        .arg("1.2bn")
+        .assert()
+        .success()
+        .stdout(predicate::eq("one billion two hundred million\n"));
+}
+
+// Tests for --lang
+
+#[test]
+fn test_lang_german_description() {
+    let mut cmd = nn();
+    cmd.arg("--lang=de")
+        .arg("42.123")
+        .assert()
+        .success()
+        .stdout(predicate::eq("42.12 (gerundet) (klein)\n"));
+}
+
+#[test]
+fn test_lang_french_duration() {
+    let mut cmd = nn();
+    cmd.arg("--lang=fr")
+        .arg("--kind=duration")
+        .arg("259200")
+        .assert()
+        .success()
+        .stdout(predicate::eq("259,200 (environ 3 jours)\n"));
+}
+
+#[test]
+fn test_lang_german_words() {
+    let mut cmd = nn();
+    cmd.arg("--lang=de")
+        .arg("--words")
+        .arg("2021")
+        .assert()
+        .success()
+        .stdout(predicate::eq("zweitausendeinundzwanzig\n"));
+}
+
+#[test]
+fn test_lang_french_words() {
+    let mut cmd = nn();
+    cmd.arg("--lang=fr")
+        .arg("--words")
+        .arg("80080")
+        .assert()
+        .success()
+        .stdout(predicate::eq("quatre-vingt mille quatre-vingts\n"));
+}
+
+#[test]
+fn test_lang_spanish_words() {
+    let mut cmd = nn();
+    cmd.arg("--lang=es")
+        .arg("--words")
+        .arg("21000000.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("veintiún millones coma cinco\n"));
+}
+
+#[test]
+fn test_lang_japanese_words() {
+    let mut cmd = nn();
+    cmd.arg("--lang=ja")
+        .arg("--words")
+        .arg("123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("一億二千三百四十五万六千七百八十九\n"));
+}
+
+#[test]
+fn test_lang_from_environment() {
+    let mut cmd = nn();
+    cmd.env("LANG", "es_ES.UTF-8")
+        .arg("5000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5,000,000 (bastante grande)\n"));
+}
+
+#[test]
+fn test_lang_unsupported_falls_back_to_english() {
+    let mut cmd = nn();
+    cmd.env("LC_ALL", "pt_BR.UTF-8")
+        .arg("5000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5,000,000 (pretty big)\n"));
+}
+
+#[test]
+fn test_lang_compare() {
+    let mut cmd = nn();
+    cmd.arg("--lang=de")
+        .arg("--compare")
+        .arg("80000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "80,000,000 (ziemlich groß) ≈ die Bevölkerung Deutschlands\n",
+        ));
+}
+
+#[test]
+fn test_lang_compare_multiple() {
+    let mut cmd = nn();
+    cmd.arg("--lang=ja")
+        .arg("--compare")
+        .arg("-b")
+        .arg("14e9")
+        .assert()
+        .success()
+        .stdout(predicate::eq("13.04 GiB (RAMに収まる) ≈ DVDの3倍\n"));
+}
+
+#[test]
+fn test_lang_compare_custom_reference() {
+    let mut cmd = nn();
+    cmd.arg("--lang=fr")
+        .arg("--compare")
+        .arg("--reference=1500:our daily signups")
+        .arg("1500")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("≈ our daily signups"));
+}
+
+#[test]
+fn test_lang_porcelain_uses_english_names() {
+    let mut cmd = nn();
+    cmd.arg("--lang=de")
+        .arg("--porcelain")
+        .arg("5000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "5000000\t5,000,000\tpretty_big\t0\t4.77 MiB\n",
+        ));
+}
+
+// Tests for --grouping
+
+#[test]
+fn test_grouping_indian() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=indian")
+        .arg("123456789.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12,34,56,789.50 (pretty big)\n"));
+}
+
+#[test]
+fn test_grouping_indian_compact() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=indian")
+        .arg("--format={compact}")
+        .write_stdin("123456789\n99999\n1234567\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12.3 crore\n1 lakh\n12.3 lakh\n"));
+}
+
+#[test]
+fn test_grouping_indian_words() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=indian")
+        .arg("--words")
+        .arg("1000000000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("one lakh crore\n"));
+}
+
+#[test]
+fn test_grouping_indian_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=indian")
+        .write_stdin("Revenue was 12.3 crore, up from 9876543")
+        .assert()
+        .success()
+        .stdout(predicate::eq("Revenue was 12,30,00,000, up from 98,76,543"));
+}
+
+#[test]
+fn test_grouping_from_locale() {
+    let mut cmd = nn();
+    cmd.env("LANG", "en_IN.UTF-8")
+        .arg("123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12,34,56,789 (pretty big)\n"));
+}
+
+#[test]
+fn test_grouping_other_locale_is_western() {
+    let mut cmd = nn();
+    cmd.env("LANG", "de_DE.UTF-8")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,234,567 (ziemlich groß)\n"));
+}
+
+#[test]
+fn test_grouping_western_overrides_locale() {
+    let mut cmd = nn();
+    cmd.env("LC_ALL", "hi_IN.UTF-8")
+        .arg("--grouping=western")
+        .arg("123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("123,456,789 (pretty big)\n"));
+}
+
+#[test]
+fn test_grouping_json_always_western() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=indian")
+        .arg("--json")
+        .arg("123456789")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"grouped\":\"123,456,789\""));
+}
+
+#[test]
+fn test_grouping_myriad() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=myriad")
+        .arg("123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1億2345万6789 (pretty big)\n"));
+}
+
+#[test]
+fn test_grouping_myriad_skips_zero_groups() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=myriad")
+        .arg("--format={grouped}")
+        .write_stdin("100000000\n100000005.25\n1234\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1億\n1億5.25\n1234\n"));
+}
+
+#[test]
+fn test_grouping_myriad_compact() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=myriad")
+        .arg("--format={compact}")
+        .write_stdin("12345\n99999999\n3.4e12\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1.2万\n1億\n3.4兆\n"));
+}
+
+#[test]
+fn test_grouping_myriad_hangul() {
+    let mut cmd = nn();
+    cmd.arg("--grouping=myriad-hangul")
+        .arg("--format={grouped} {compact}")
+        .arg("--")
+        .arg("-123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-1억 2345만 6789 -1.2억\n"));
+}
+
+// Tests for digits in other scripts
+
+#[test]
+fn test_native_digits_arabic_indic() {
+    let mut cmd = nn();
+    cmd.arg("١٢٣٤٥")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12,345 (medium)\n"));
+}
+
+#[test]
+fn test_native_digits_arabic_decimal_separator() {
+    let mut cmd = nn();
+    cmd.arg("١٢٣٫٤٥")
+        .assert()
+        .success()
+        .stdout(predicate::eq("123.45 (small)\n"));
+}
+
+#[test]
+fn test_native_digits_other_scripts() {
+    let mut cmd = nn();
+    cmd.arg("--format={grouped}")
+        .write_stdin("۱۲۳۴۵۶\n१२३४५६७\n১২৩৪\n๑๒๓๔๕\n１２３４５６\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "123,456\n1,234,567\n1,234\n12,345\n123,456\n",
+        ));
+}
+
+#[test]
+fn test_native_digits_in_text() {
+    let mut cmd = nn();
+    cmd.write_stdin("السعر ١٢٣٤٥ ريال")
+        .assert()
+        .success()
+        .stdout(predicate::eq("السعر 12,345 ريال"));
+}
+
+#[test]
+fn test_native_digits_output() {
+    let mut cmd = nn();
+    cmd.arg("--digits=devanagari")
+        .arg("--grouping=indian")
+        .write_stdin("मूल्य १२३४५६७ रुपये")
+        .assert()
+        .success()
+        .stdout(predicate::eq("मूल्य १२,३४,५६७ रुपये"));
+}
+
+#[test]
+fn test_native_digits_output_description() {
+    let mut cmd = nn();
+    cmd.arg("--digits=thai")
+        .arg("--magnitude")
+        .arg("1234567.891")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "๑,๒๓๔,๕๖๗.๘๙ (rounded) (pretty big, ~๑๐^๖)\n",
+        ));
+}
+
+#[test]
+fn test_native_digits_from_locale() {
+    let mut cmd = nn();
+    cmd.env("LC_ALL", "ar_EG.UTF-8")
+        .arg("--digits=locale")
+        .arg("12345")
+        .assert()
+        .success()
+        .stdout(predicate::eq("١٢,٣٤٥ (medium)\n"));
+}
+
+#[test]
+fn test_native_digits_json_uses_ascii() {
+    let mut cmd = nn();
+    cmd.arg("--json")
+        .arg("--digits=arabic-indic")
+        .arg("x ١٢٣٤ y")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"output\":\"x 1,234 y\""));
+}
+
+// Tests for --sci and --eng
+
+#[test]
+fn test_sci() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("123456")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1.23×10^5 (medium)\n"));
+}
+
+#[test]
+fn test_sci_keeps_significant_zeros() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("--sig-figs=4")
+        .arg("999999")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1.000×10^6 (medium)\n"));
+}
+
+#[test]
+fn test_eng() {
+    let mut cmd = nn();
+    cmd.arg("--eng")
+        .arg("--sig-figs=4")
+        .arg("0.000123456")
+        .assert()
+        .success()
+        .stdout(predicate::eq("123.5×10^-6 (microscopic)\n"));
+}
+
+#[test]
+fn test_eng_negative() {
+    let mut cmd = nn();
+    cmd.arg("--eng")
+        .arg("--")
+        .arg("-42000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-42.0×10^3 (medium)\n"));
+}
+
+#[test]
+fn test_exponent_styles() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("--exponent-style=superscript")
+        .arg("0.00000602")
+        .assert()
+        .success()
+        .stdout(predicate::eq("6.02×10⁻⁶ (microscopic)\n"));
+
+    let mut cmd = nn();
+    cmd.arg("--eng")
+        .arg("--exponent-style=e")
+        .arg("5000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5.00e6 (pretty big)\n"));
+}
+
+#[test]
+fn test_sci_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("--sig-figs=2")
+        .write_stdin("Distance: 149600000 km")
+        .assert()
+        .success()
+        .stdout(predicate::eq("Distance: 1.5×10^8 km"));
+}
+
+#[test]
+fn test_sci_and_eng_conflict() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("--eng")
+        .arg("42")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_sig_figs_out_of_range() {
+    let mut cmd = nn();
+    cmd.arg("--sci")
+        .arg("--sig-figs=0")
+        .arg("42")
+        .assert()
+        .failure();
+}
+
+// Tests for --si-unit
+
+#[test]
+fn test_si_unit_small() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=s")
+        .arg("0.0000125")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12.5 µs (microscopic)\n"));
+}
+
+#[test]
+fn test_si_unit_large() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=Hz")
+        .arg("2400000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2.4 GHz (extremely big)\n"));
+}
+
+#[test]
+fn test_si_unit_rounds_up_to_next_prefix() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=W")
+        .arg("999999")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1 MW (medium)\n"));
+}
+
+#[test]
+fn test_si_unit_negative() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=W")
+        .arg("--")
+        .arg("-0.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-500 mW (tiny)\n"));
+}
+
+#[test]
+fn test_si_unit_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=s")
+        .write_stdin("0.0000125 s, then 1500 ms over 3000 hops in 5 seconds")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "12.5 µs, then 1.5 s over 3,000 hops in 5 seconds",
+        ));
+}
+
+#[test]
+fn test_si_unit_in_text_with_prefix() {
+    let mut cmd = nn();
+    cmd.arg("--si-unit=Hz")
+        .write_stdin("CPU at 3500MHz, radio at 0.8 kHz")
+        .assert()
+        .success()
+        .stdout(predicate::eq("CPU at 3.5 GHz, radio at 800 Hz"));
+}
+
+// Tests for --duration
+
+#[test]
+fn test_duration_compare_in_seconds() {
+    let mut cmd = nn();
+    cmd.arg("--duration=ms")
+        .arg("--compare")
+        .arg("5400000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1h 30m (about 1.5 hours) ≈ a feature film\n"));
+}
+
+#[test]
+fn test_duration_seconds() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("93784")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1d 2h 3m 4s (about 1.1 days)\n"));
+}
+
+#[test]
+fn test_duration_under_a_second() {
+    let mut cmd = nn();
+    cmd.arg("--duration=ms")
+        .arg("1.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1.5 ms (less than a second)\n"));
+}
+
+#[test]
+fn test_duration_microseconds() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("0.0000125")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12.5 µs (less than a second)\n"));
+}
+
+#[test]
+fn test_duration_nanoseconds() {
+    let mut cmd = nn();
+    cmd.arg("--duration=ns")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1.23 ms (less than a second)\n"));
+}
+
+#[test]
+fn test_duration_negative() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("--")
+        .arg("-3725.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-1h 2m 5.5s (about 1 hour)\n"));
+}
+
+#[test]
+fn test_duration_rounding_carries() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("59.999")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1m (about 60 seconds)\n"));
+}
+
+#[test]
+fn test_duration_largest_unit() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("--largest-unit=h")
+        .arg("93784")
+        .assert()
+        .success()
+        .stdout(predicate::eq("26h 3m 4s (about 1.1 days)\n"));
+}
+
+#[test]
+fn test_duration_smallest_unit() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("--smallest-unit=ms")
+        .arg("3.25")
+        .assert()
+        .success()
+        .stdout(predicate::eq("3s 250ms (about 3.3 seconds)\n"));
+}
+
+#[test]
+fn test_duration_precision() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("--smallest-unit=m")
+        .arg("--precision=1")
+        .arg("93784")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1d 2h 3.1m (about 1.1 days)\n"));
+}
+
+#[test]
+fn test_duration_requires_duration() {
+    let mut cmd = nn();
+    cmd.arg("--precision=1").arg("93784").assert().failure();
+}
+
+#[test]
+fn test_duration_parse_input() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("1h30m")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1h 30m (about 1.5 hours)\n"));
+}
+
+#[test]
+fn test_duration_input_needs_duration() {
+    let mut cmd = nn();
+    cmd.arg("10ms")
+        .assert()
+        .success()
+        .stdout(predicate::eq("10ms\n"));
+}
+
+#[test]
+fn test_duration_parse_input_in_unit() {
+    let mut cmd = nn();
+    cmd.arg("--duration=ms")
+        .arg("1h30m")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1h 30m (about 1.5 hours)\n"));
+}
+
+#[test]
+fn test_duration_parse_minutes_with_duration() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .arg("5m")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5m (about 5 minutes)\n"));
+}
+
+#[test]
+fn test_duration_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .write_stdin("up 93784 seconds")
+        .assert()
+        .success()
+        .stdout(predicate::eq("up 1d 2h 3m 4s"));
+}
+
+#[test]
+fn test_duration_in_text_uses_unit_word() {
+    let mut cmd = nn();
+    cmd.arg("--duration=s")
+        .write_stdin("took 1500 ms after 3 retries\n")
+        .assert()
+        .success()
+        .stdout(predicate::eq("took 1.5s after 3 retries\n"));
+}
+
+// Tests for --timestamps
+
+#[test]
+fn test_timestamps_seconds() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-11-17T22:13:20Z")
+        .arg("1700000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20Z (3 days ago)\n"));
+}
+
+#[test]
+fn test_timestamps_milliseconds() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=1700000000")
+        .arg("1700000000123")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20.123Z (just now)\n"));
+}
+
+#[test]
+fn test_timestamps_microseconds() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=1700000000")
+        .arg("1700000000123456")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20.123456Z (just now)\n"));
+}
+
+#[test]
+fn test_timestamps_nanoseconds() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=1700000000")
+        .arg("1700000000123456789")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20.123456789Z (just now)\n"));
+}
+
+#[test]
+fn test_timestamps_fractional_seconds() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=1700000000")
+        .arg("1700000000.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20.500Z (just now)\n"));
+}
+
+#[test]
+fn test_timestamps_in_the_future() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=1700000000")
+        .arg("1700007200")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-15T00:13:20Z (in 2 hours)\n"));
+}
+
+#[test]
+fn test_timestamps_leap_day() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2024-03-01")
+        .arg("1709164800")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2024-02-29T00:00:00Z (1 day ago)\n"));
+}
+
+#[test]
+fn test_timestamps_utc_offset() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-11-17T22:13:20Z")
+        .arg("--utc-offset=+05:30")
+        .arg("1700000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-15T03:43:20+05:30 (3 days ago)\n"));
+}
+
+#[test]
+fn test_timestamps_negative_utc_offset() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-11-17T22:13:20Z")
+        .arg("--utc-offset")
+        .arg("-08:00")
+        .arg("1700000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T14:13:20-08:00 (3 days ago)\n"));
+}
+
+#[test]
+fn test_timestamps_invalid_utc_offset() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--utc-offset=+25:00")
+        .arg("1700000000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid UTC offset"));
+}
+
+#[test]
+fn test_timestamps_now_with_offset() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-11-15T00:13:20+02:00")
+        .arg("1700000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20Z (just now)\n"));
+}
+
+#[test]
+fn test_timestamps_now_out_of_range() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=-1e300")
+        .arg("1700000000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid time '-1e300'"));
+}
+
+#[test]
+fn test_timestamps_now_invalid_day() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-02-29")
+        .arg("1700000000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid time '2023-02-29'"));
+}
+
+#[test]
+fn test_timestamps_lang() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("--now=2023-11-17T22:13:20Z")
+        .arg("--lang=de")
+        .arg("1700000000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("2023-11-14T22:13:20Z (vor 3 Tagen)\n"));
+}
+
+#[test]
+fn test_timestamps_implausible_number() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .arg("1700")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,700 (medium)\n"));
+}
+
+#[test]
+fn test_timestamps_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--timestamps")
+        .write_stdin("request at 1700000000123 took 2500 ms")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "request at 2023-11-14T22:13:20.123Z took 2,500 ms",
+        ));
+}
+
+#[test]
+fn test_timestamps_requires_timestamps() {
+    let mut cmd = nn();
+    cmd.arg("--now=1700000000")
+        .arg("1700000000")
+        .assert()
+        .failure();
+}
+
+// Tests for --percent, --per-mille and --basis-points
+
+#[test]
+fn test_percent() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("0.0523")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5.23% (about 1 in 19)\n"));
+}
+
+#[test]
+fn test_percent_precision() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("--precision=1")
+        .arg("0.0523")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5.2% (about 1 in 19)\n"));
+}
+
+#[test]
+fn test_percent_trims_zeros() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("0.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("50% (about 1 in 2)\n"));
+}
+
+#[test]
+fn test_percent_over_one() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("1.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("150% (about 1.5 times)\n"));
+}
+
+#[test]
+fn test_per_mille() {
+    let mut cmd = nn();
+    cmd.arg("--per-mille")
+        .arg("0.0523")
+        .assert()
+        .success()
+        .stdout(predicate::eq("52.3‰ (about 1 in 19)\n"));
+}
+
+#[test]
+fn test_basis_points() {
+    let mut cmd = nn();
+    cmd.arg("--basis-points")
+        .arg("0.0523")
+        .assert()
+        .success()
+        .stdout(predicate::eq("523 bp (about 1 in 19)\n"));
+}
+
+#[test]
+fn test_percent_conflicts_with_per_mille() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("--per-mille")
+        .arg("0.5")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_percent_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .write_stdin("error rate 0.0523 over 1000 requests")
+        .assert()
+        .success()
+        .stdout(predicate::eq("error rate 5.23% over 1,000 requests"));
+}
+
+#[test]
+fn test_percent_input() {
+    let mut cmd = nn();
+    cmd.arg("12.5%")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12.5% (about 1 in 8)\n"));
+}
+
+#[test]
+fn test_percent_input_keeps_precision() {
+    let mut cmd = nn();
+    cmd.arg("0.001%")
+        .assert()
+        .success()
+        .stdout(predicate::eq("0.001% (about 1 in 100,000)\n"));
+}
+
+#[test]
+fn test_percent_nan() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("nan")
+        .assert()
+        .success()
+        .stdout(predicate::eq("NaN%\n"));
+}
+
+#[test]
+fn test_percent_input_most() {
+    let mut cmd = nn();
+    cmd.arg("75%")
+        .assert()
+        .success()
+        .stdout(predicate::eq("75% (about 8 in 10)\n"));
+}
+
+#[test]
+fn test_per_mille_input() {
+    let mut cmd = nn();
+    cmd.arg("5‰")
+        .assert()
+        .success()
+        .stdout(predicate::eq("5‰ (about 1 in 200)\n"));
+}
+
+#[test]
+fn test_basis_points_input() {
+    let mut cmd = nn();
+    cmd.arg("25bp")
+        .assert()
+        .success()
+        .stdout(predicate::eq("25 bp (about 1 in 400)\n"));
+}
+
+#[test]
+fn test_percent_input_converted() {
+    let mut cmd = nn();
+    cmd.arg("--basis-points")
+        .arg("12.5%")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1250 bp (about 1 in 8)\n"));
+}
+
+#[test]
+fn test_percent_input_json() {
+    let mut cmd = nn();
+    cmd.arg("--json")
+        .arg("12.5%")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"value\":0.125"));
+}
+
+#[test]
+fn test_percent_compare() {
+    let mut cmd = nn();
+    cmd.arg("--percent")
+        .arg("--compare")
+        .arg("0.16")
+        .assert()
+        .success()
+        .stdout(predicate::eq("16% (about 1 in 6) ≈ rolling a six\n"));
+}
+
+#[test]
+fn test_percent_lang() {
+    let mut cmd = nn();
+    cmd.arg("--lang=fr")
+        .arg("12.5%")
+        .assert()
+        .success()
+        .stdout(predicate::eq("12.5% (environ 1 sur 8)\n"));
+}
+
+// Tests for --currency
+
+#[test]
+fn test_currency() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("$1,234.50 (about 1.2 thousand dollars)\n"));
+}
+
+#[test]
+fn test_currency_no_minor_units() {
+    let mut cmd = nn();
+    cmd.arg("--currency=JPY")
+        .arg("1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("¥1,235 (rounded) (about 1.2 thousand JPY)\n"));
+}
+
+#[test]
+fn test_currency_three_minor_units() {
+    let mut cmd = nn();
+    cmd.arg("--currency=BHD")
+        .arg("1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("BHD 1,234.500 (about 1.2 thousand BHD)\n"));
+}
+
+#[test]
+fn test_currency_lowercase_code() {
+    let mut cmd = nn();
+    cmd.arg("--currency=gbp")
+        .arg("5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("£5.00 (about 5 GBP)\n"));
+}
+
+#[test]
+fn test_currency_unknown() {
+    let mut cmd = nn();
+    cmd.arg("--currency=XYZ")
+        .arg("5")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown currency 'XYZ'"));
+}
+
+#[test]
+fn test_currency_symbol_after_amount() {
+    let mut cmd = nn();
+    cmd.arg("--currency=EUR")
+        .arg("--lang=de")
+        .arg("1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("1,234.50 € (etwa 1.2 Tausend EUR)\n"));
+}
+
+#[test]
+fn test_currency_negative() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("--")
+        .arg("-1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("-$1,234.50 (about 1.2 thousand dollars)\n"));
+}
+
+#[test]
+fn test_currency_accounting() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("--accounting")
+        .arg("--")
+        .arg("-1234.5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("($1,234.50) (about 1.2 thousand dollars)\n"));
+}
+
+#[test]
+fn test_currency_indian_grouping() {
+    let mut cmd = nn();
+    cmd.arg("--currency=INR")
+        .arg("--grouping=indian")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("₹12,34,567.00 (about 1.2 million INR)\n"));
+}
+
+#[test]
+fn test_currency_symbol_input() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("€1234")
+        .assert()
+        .success()
+        .stdout(predicate::eq("€1,234.00 (about 1.2 thousand EUR)\n"));
+}
+
+#[test]
+fn test_currency_shared_symbol() {
+    let mut cmd = nn();
+    cmd.arg("--currency=CAD")
+        .arg("$5")
+        .assert()
+        .success()
+        .stdout(predicate::eq("CA$5.00 (about 5 CAD)\n"));
+}
+
+#[test]
+fn test_currency_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .write_stdin("paid $1234.5 and €1234 for 3000 items, refund -$20")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "paid $1,234.50 and €1,234.00 for 3,000 items, refund -$20.00",
+        ));
+}
+
+#[test]
+fn test_currency_compares_as_money() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("--compare")
+        .arg("30000")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "$30,000.00 (about 30 thousand dollars) ≈ a new car\n",
+        ));
+}
+
+#[test]
+fn test_currency_compares_with_own_references_only() {
+    let mut cmd = nn();
+    cmd.arg("--currency=JPY")
+        .arg("--compare")
+        .arg("30000")
+        .assert()
+        .success()
+        .stdout(predicate::eq("¥30,000 (about 30 thousand JPY)\n"));
+
+    let mut cmd = nn();
+    cmd.arg("--currency=JPY")
+        .arg("--compare")
+        .arg("--reference=500:a bowl of ramen")
+        .arg("30000")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "¥30,000 (about 30 thousand JPY) ≈ 60 × a bowl of ramen\n",
+        ));
+}
+
+#[test]
+fn test_currency_describes_money_in_currency() {
+    let mut cmd = nn();
+    cmd.arg("--currency=JPY")
+        .arg("--kind=money")
+        .arg("1234567")
+        .assert()
+        .success()
+        .stdout(predicate::eq("¥1,234,567 (about 1.2 million JPY)\n"));
+}
+
+#[test]
+fn test_currency_huge_amount_has_no_cents() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("1e30")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "$1,000,000,000,000,000,019,884,624,838,656.00 ",
+        ));
+}
+
+#[test]
+fn test_currency_grouped_input() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .arg("$1,234.50")
+        .assert()
+        .success()
+        .stdout(predicate::eq("$1,234.50 (about 1.2 thousand dollars)\n"));
+}
+
+#[test]
+fn test_currency_grouped_in_text() {
+    let mut cmd = nn();
+    cmd.arg("--currency=USD")
+        .write_stdin("paid $1,234.50 and €12,345,678")
+        .assert()
+        .success()
+        .stdout(predicate::eq("paid $1,234.50 and €12,345,678.00"));
+}
+
+#[test]
+fn test_accounting_requires_currency() {
+    let mut cmd = nn();
+    cmd.arg("--accounting").arg("5").assert().failure();
+}